@@ -0,0 +1,356 @@
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+
+lazy_static::lazy_static! {
+    // Encoders listed by `ffmpeg -encoders`, probed once per process and
+    // shared by the recorder's codec check and the export encoder choice.
+    // Replaced by refresh_encoders
+    static ref ENCODERS: Mutex<Option<Vec<String>>> = Mutex::new(None);
+}
+
+// Resolve a tool: an explicit path from the environment (VISUALCODER_FFMPEG,
+// VISUALCODER_FFPROBE) wins, then a binary shipped next to the app executable
+// (a Tauri sidecar), then the bare name so the OS searches PATH
+fn tool_path(name: &str, env_var: &str) -> PathBuf {
+    if let Some(path) = std::env::var_os(env_var).filter(|path| !path.is_empty()) {
+        return PathBuf::from(path);
+    }
+    let bundled = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(format!("{}{}", name, std::env::consts::EXE_SUFFIX))));
+    if let Some(bundled) = bundled.filter(|path| path.is_file()) {
+        return bundled;
+    }
+    PathBuf::from(name)
+}
+
+// FFmpeg binary to run for recording, export and probing
+pub fn ffmpeg_path() -> PathBuf {
+    tool_path("ffmpeg", "VISUALCODER_FFMPEG")
+}
+
+pub fn ffprobe_path() -> PathBuf {
+    tool_path("ffprobe", "VISUALCODER_FFPROBE")
+}
+
+// Names of the encoders the FFmpeg build lists, None if it couldn't be run
+fn list_encoders() -> Option<Vec<String>> {
+    let output = match Command::new(ffmpeg_path()).args(["-hide_banner", "-encoders"]).output() {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to query FFmpeg encoders: {}", e);
+            return None;
+        }
+    };
+
+    if !output.status.success() {
+        eprintln!("Failed to query FFmpeg encoders: {}", error_summary(&String::from_utf8_lossy(&output.stderr)));
+        return None;
+    }
+
+    // A legend ("Encoders:", " V..... = Video", ...) comes first and ends at
+    // " ------"; after it, lines look like " V....D libx264   libx264 H.264 / AVC ..."
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .skip_while(|line| !line.trim_start().starts_with("------"))
+            .skip(1)
+            .filter_map(|line| line.split_whitespace().nth(1))
+            .map(|encoder| encoder.to_string())
+            .collect(),
+    )
+}
+
+// Check whether the FFmpeg build lists an encoder (e.g. "libx265",
+// "h264_nvenc"). Hardware encoders also show up here when FFmpeg was built
+// with them, even if no matching GPU is present, so this is a first filter
+// rather than a guarantee. The list is probed on first use and cached; a
+// failed probe isn't cached, so a later call tries again.
+pub fn encoder_available(name: &str) -> bool {
+    let mut encoders = ENCODERS.lock().unwrap();
+    if encoders.is_none() {
+        *encoders = list_encoders();
+    }
+    encoders.as_ref().is_some_and(|encoders| encoders.iter().any(|encoder| encoder == name))
+}
+
+// Probe the encoders again, e.g. after the user installed new GPU drivers
+// or another FFmpeg build without restarting. Returns the encoder names
+#[tauri::command]
+pub fn refresh_encoders() -> Result<Vec<String>, String> {
+    let listed = list_encoders().ok_or("Failed to query FFmpeg encoders")?;
+    *ENCODERS.lock().unwrap() = Some(listed.clone());
+    Ok(listed)
+}
+
+// Frames at the given timestamps as base64 JPEGs, scaled to `width` with
+// the height following the aspect ratio, in the same order as `timestamps`.
+// Each timestamp is its own input with a fast seek (-ss before -i), all in
+// one FFmpeg run. A select filter over one input would decode the whole
+// video up to the last timestamp, which is slow for long recordings.
+#[tauri::command]
+pub async fn extract_thumbnails(input_path: String, timestamps: Vec<f64>, width: u32) -> Result<Vec<String>, String> {
+    use base64::Engine;
+
+    if timestamps.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !(16..=3840).contains(&width) {
+        return Err(format!("Thumbnail width must be between 16 and 3840, got {}", width));
+    }
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let temp_dir = std::env::temp_dir().join(format!("visualcoder_thumbs_{}_{}", std::process::id(), nanos));
+    std::fs::create_dir_all(&temp_dir)
+        .map_err(|e| format!("Failed to create temp directory: {}", e))?;
+
+    let mut args: Vec<String> = vec!["-y".to_string(), "-v".to_string(), "error".to_string()];
+    for timestamp in &timestamps {
+        args.extend([
+            "-ss".to_string(), format!("{:.3}", timestamp.max(0.0)),
+            "-i".to_string(), input_path.clone(),
+        ]);
+    }
+    let paths: Vec<PathBuf> = (0..timestamps.len())
+        .map(|i| temp_dir.join(format!("thumb_{:04}.jpg", i)))
+        .collect();
+    for (i, path) in paths.iter().enumerate() {
+        args.extend([
+            "-map".to_string(), format!("{}:v:0", i),
+            "-frames:v".to_string(), "1".to_string(),
+            "-vf".to_string(), format!("scale={}:-2", width),
+            "-q:v".to_string(), "4".to_string(),
+            path.to_string_lossy().to_string(),
+        ]);
+    }
+
+    let output = Command::new(ffmpeg_path()).args(&args).output();
+    let result = match output {
+        Err(e) => Err(format!("Failed to run FFmpeg: {}", e)),
+        Ok(output) if !output.status.success() => {
+            Err(format!("Failed to extract thumbnails: {}", error_summary(&String::from_utf8_lossy(&output.stderr))))
+        }
+        Ok(_) => paths
+            .iter()
+            .zip(&timestamps)
+            .map(|(path, timestamp)| {
+                std::fs::read(path)
+                    .map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes))
+                    // Seeking past the end yields no frame
+                    .map_err(|_| format!("No frame at {:.3}s", timestamp))
+            })
+            .collect(),
+    };
+
+    std::fs::remove_dir_all(&temp_dir).ok();
+    result
+}
+
+// Single-frame version of extract_thumbnails
+#[tauri::command]
+pub async fn extract_thumbnail(input_path: String, timestamp_secs: f64, width: u32) -> Result<String, String> {
+    extract_thumbnails(input_path, vec![timestamp_secs], width)
+        .await?
+        .pop()
+        .ok_or_else(|| format!("No frame at {:.3}s", timestamp_secs))
+}
+
+// Pick the line of an FFmpeg log that says what went wrong. FFmpeg prints
+// its banner, stream info and a summary around the real error, so prefer the
+// last line that mentions an error and fall back to the last non-empty line
+pub fn error_summary(stderr: &str) -> String {
+    let lines: Vec<&str> = stderr.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+    let is_error = |line: &&&str| {
+        let lower = line.to_lowercase();
+        lower.contains("error") || lower.contains("invalid") || lower.contains("no such file")
+            || lower.contains("not found") || lower.contains("unknown")
+    };
+    lines
+        .iter()
+        .rev()
+        .find(is_error)
+        .or_else(|| lines.last())
+        .map(|line| line.to_string())
+        .unwrap_or_else(|| "FFmpeg exited without an error message".to_string())
+}
+
+// Why ffprobe couldn't answer. A missing binary is its own case so callers
+// can report it like a missing FFmpeg instead of a broken file
+#[derive(Debug)]
+pub enum ProbeError {
+    NotFound,        // ffprobe couldn't be started (not installed or not on PATH)
+    Failed(String),  // It ran but couldn't read the file, or its output
+}
+
+impl From<ProbeError> for String {
+    fn from(error: ProbeError) -> Self {
+        match error {
+            ProbeError::NotFound => format!("ffprobe not found at {}", ffprobe_path().display()),
+            ProbeError::Failed(message) => message,
+        }
+    }
+}
+
+// Run ffprobe with the given arguments and return its output
+fn run_ffprobe(args: &[&str]) -> Result<std::process::Output, ProbeError> {
+    Command::new(ffprobe_path()).args(args).output().map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => ProbeError::NotFound,
+        _ => ProbeError::Failed(format!("Failed to run ffprobe: {}", e)),
+    })
+}
+
+// Whether a media file has at least one audio stream
+pub fn has_audio_stream(path: &str) -> Result<bool, ProbeError> {
+    let output = run_ffprobe(&["-v", "error", "-select_streams", "a", "-show_entries", "stream=index", "-of", "csv=p=0", path])?;
+    if !output.status.success() {
+        return Err(ProbeError::Failed(format!("ffprobe failed: {}", String::from_utf8_lossy(&output.stderr))));
+    }
+    Ok(!String::from_utf8_lossy(&output.stdout).trim().is_empty())
+}
+
+// Basic properties of a video file, as reported by ffprobe
+#[derive(serde::Serialize, Clone)]
+pub struct VideoMetadata {
+    pub width: u32,
+    pub height: u32,
+    pub duration: f64,      // seconds
+    pub fps: f64,
+    pub codec: String,      // e.g. "h264"
+    pub bitrate: Option<u64>,  // bits per second, when the container reports it
+}
+
+// ffprobe writes rates as fractions, e.g. "30000/1001"
+fn parse_frame_rate(rate: &str) -> Option<f64> {
+    let (num, den) = rate.split_once('/')?;
+    let num: f64 = num.parse().ok()?;
+    let den: f64 = den.parse().ok()?;
+    if den > 0.0 { Some(num / den) } else { None }
+}
+
+// Numbers in ffprobe's JSON output are quoted strings
+fn json_number<T: std::str::FromStr>(value: &serde_json::Value) -> Option<T> {
+    value.as_str().and_then(|text| text.parse().ok())
+}
+
+// Read the first video stream and the container format of a file
+#[tauri::command]
+pub fn probe_video(path: String) -> Result<VideoMetadata, String> {
+    read_video_metadata(&path).map_err(String::from)
+}
+
+// probe_video for the backend, keeping a missing ffprobe apart
+pub fn read_video_metadata(path: &str) -> Result<VideoMetadata, ProbeError> {
+    let output = run_ffprobe(&["-v", "error", "-show_streams", "-show_format", "-of", "json", path])?;
+    if !output.status.success() {
+        return Err(ProbeError::Failed(format!(
+            "{} is not a readable media file: {}",
+            path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let probe: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| ProbeError::Failed(format!("Failed to parse ffprobe output: {}", e)))?;
+    let stream = probe["streams"]
+        .as_array()
+        .and_then(|streams| streams.iter().find(|stream| stream["codec_type"] == "video"))
+        .ok_or_else(|| ProbeError::Failed(format!("{} has no video stream", path)))?;
+    let format = &probe["format"];
+
+    let width = stream["width"].as_u64().unwrap_or(0) as u32;
+    let height = stream["height"].as_u64().unwrap_or(0) as u32;
+    if width == 0 || height == 0 {
+        return Err(ProbeError::Failed(format!("{} has no video dimensions", path)));
+    }
+
+    // avg_frame_rate is "0/0" for some streams, so fall back to r_frame_rate
+    let fps = ["avg_frame_rate", "r_frame_rate"]
+        .iter()
+        .filter_map(|key| stream[*key].as_str().and_then(parse_frame_rate))
+        .find(|fps| *fps > 0.0)
+        .unwrap_or(0.0);
+
+    Ok(VideoMetadata {
+        width,
+        height,
+        duration: json_number(&format["duration"])
+            .or_else(|| json_number(&stream["duration"]))
+            .unwrap_or(0.0),
+        fps,
+        codec: stream["codec_name"].as_str().unwrap_or("unknown").to_string(),
+        bitrate: json_number(&format["bit_rate"]).or_else(|| json_number(&stream["bit_rate"])),
+    })
+}
+
+// Whether a tool could be run, and the version it reported
+#[derive(serde::Serialize, Clone)]
+pub struct ToolStatus {
+    pub available: bool,
+    pub version: Option<String>,  // e.g. "6.1.1" or "2024-03-04-git-e30369bc1c"
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct DependencyStatus {
+    pub ffmpeg: ToolStatus,
+    pub ffprobe: ToolStatus,
+}
+
+// Run `<tool> -version` and read the version from its first line,
+// "ffmpeg version 6.1.1-full_build-www.gyan.dev Copyright ..."
+fn tool_status(tool: &str, path: PathBuf) -> ToolStatus {
+    let output = match Command::new(&path).arg("-version").output() {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            eprintln!("{} -version exited with {}", tool, output.status);
+            return ToolStatus { available: false, version: None };
+        }
+        Err(e) => {
+            eprintln!("{} not found at {}: {}", tool, path.display(), e);
+            return ToolStatus { available: false, version: None };
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let version = stdout
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().skip_while(|word| *word != "version").nth(1))
+        .map(|version| version.to_string());
+    ToolStatus { available: true, version }
+}
+
+// Check that FFmpeg and ffprobe can be run, so the frontend can ask the
+// user to install them instead of failing on the first recording or export
+#[tauri::command]
+pub fn check_dependencies() -> DependencyStatus {
+    DependencyStatus {
+        ffmpeg: tool_status("ffmpeg", ffmpeg_path()),
+        ffprobe: tool_status("ffprobe", ffprobe_path()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_summary_picks_the_last_error_line() {
+        let cases = [
+            (
+                "ffmpeg version 6.0\n  built with gcc\nInput #0, mov\n[libx264 @ 0x1] Error: invalid preset\nConversion failed!\n",
+                "[libx264 @ 0x1] Error: invalid preset",
+            ),
+            ("in.mp4: No such file or directory\n", "in.mp4: No such file or directory"),
+            // Without an error-looking line, the last line is the best guess
+            ("ffmpeg version 6.0\nsomething went sideways\n\n", "something went sideways"),
+            ("", "FFmpeg exited without an error message"),
+        ];
+        for (stderr, expected) in cases {
+            assert_eq!(error_summary(stderr), expected);
+        }
+    }
+}
@@ -1,8 +1,13 @@
+mod ffmpeg;
 mod recorder;
 
 use recorder::RecorderState;
-use tauri::Manager;
-use std::process::Command;
+use tauri::{Emitter, Manager};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -28,6 +33,13 @@ fn get_temp_video_path() -> Result<String, String> {
     Ok(path.to_string_lossy().to_string())
 }
 
+// Free bytes on the drive holding `path`, for the UI to show before long
+// recordings or exports
+#[tauri::command]
+fn get_free_space(path: String) -> Result<u64, String> {
+    recorder::free_disk_space(std::path::Path::new(&path))
+}
+
 #[tauri::command]
 fn get_videos_dir_path() -> Result<String, String> {
     let videos_dir = dirs::video_dir().ok_or("Could not find Videos directory")?;
@@ -35,11 +47,81 @@ fn get_videos_dir_path() -> Result<String, String> {
 }
 
 
+// Windows error for a rename across drives (ERROR_NOT_SAME_DEVICE)
+const ERROR_NOT_SAME_DEVICE: i32 = 17;
+const COPY_CHUNK_BYTES: usize = 8 * 1024 * 1024;
+
+// Copy in chunks, emitting "move-progress" (0-1) after each one
+fn copy_with_progress(app: &tauri::AppHandle, from: &std::path::Path, to: &std::path::Path) -> Result<(), String> {
+    use std::io::Write;
+    
+    let mut source = std::fs::File::open(from).map_err(|e| format!("Failed to open video: {}", e))?;
+    let total = source.metadata().map(|m| m.len()).unwrap_or(0);
+    let mut dest = std::fs::File::create(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+    let mut buf = vec![0u8; COPY_CHUNK_BYTES];
+    let mut copied: u64 = 0;
+    loop {
+        let read = source.read(&mut buf).map_err(|e| format!("Failed to read video: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        dest.write_all(&buf[..read]).map_err(|e| format!("Failed to copy video: {}", e))?;
+        copied += read as u64;
+        if total > 0 {
+            app.emit("move-progress", copied as f64 / total as f64).ok();
+        }
+    }
+    dest.flush().map_err(|e| format!("Failed to copy video: {}", e))
+}
+
+// `dir/name`, or "name (1).ext", "name (2).ext", ... if that already exists
+fn unused_path(dir: &std::path::Path, name: &str) -> std::path::PathBuf {
+    let path = dir.join(name);
+    if !path.exists() {
+        return path;
+    }
+    let name_path = std::path::Path::new(name);
+    let stem = name_path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = name_path.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    (1..)
+        .map(|n| dir.join(format!("{} ({}){}", stem, n, extension)))
+        .find(|candidate| !candidate.exists())
+        .unwrap_or(path)
+}
+
+// Returns the path actually used: an existing file with the same name is
+// kept and a " (n)" suffix added, unless overwrite is set
 #[tauri::command]
-async fn move_video_to_videos(temp_path: String, final_name: String) -> Result<String, String> {
+async fn move_video_to_videos(
+    app: tauri::AppHandle,
+    temp_path: String,
+    final_name: String,
+    overwrite: Option<bool>,
+) -> Result<String, String> {
     let videos_dir = dirs::video_dir().ok_or("Could not find Videos directory")?;
-    let final_path = videos_dir.join(&final_name);
-    std::fs::copy(&temp_path, &final_path).map_err(|e| format!("Failed to copy video: {}", e))?;
+    let final_path = if overwrite.unwrap_or(false) {
+        videos_dir.join(&final_name)
+    } else {
+        unused_path(&videos_dir, &final_name)
+    };
+    
+    // On the same drive a rename is instant
+    match std::fs::rename(&temp_path, &final_path) {
+        Ok(()) => return Ok(final_path.to_string_lossy().to_string()),
+        Err(e) if e.raw_os_error() == Some(ERROR_NOT_SAME_DEVICE) => {}
+        Err(e) => return Err(format!("Failed to move video: {}", e)),
+    }
+    
+    // Across drives copy instead, off the async runtime since large
+    // recordings take a while
+    let (from, to) = (std::path::PathBuf::from(&temp_path), final_path.clone());
+    let copied = tauri::async_runtime::spawn_blocking(move || copy_with_progress(&app, &from, &to))
+        .await
+        .map_err(|e| format!("Copy task failed: {}", e))?;
+    if let Err(e) = copied {
+        std::fs::remove_file(&final_path).ok(); // Don't leave a partial copy
+        return Err(e);
+    }
     std::fs::remove_file(&temp_path).ok(); // Cleanup temp, ignore errors
     Ok(final_path.to_string_lossy().to_string())
 }
@@ -49,44 +131,352 @@ fn delete_temp_video(temp_path: String) -> Result<(), String> {
     std::fs::remove_file(&temp_path).map_err(|e| format!("Failed to delete temp video: {}", e))
 }
 
+// Result of trim_video. Stream copy is near-instant but can only start at a
+// keyframe, so the cut may land up to one GOP (a few seconds) early; precise
+// trimming re-encodes and cuts on the exact frame, taking roughly as long as
+// a normal export of the trimmed range.
+#[derive(serde::Serialize, Clone)]
+struct TrimResult {
+    output_path: String,
+    frame_accurate: bool,  // True when re-encoded for an exact cut
+}
+
+// The browser and ffprobe can disagree on a clip's length by a frame or two,
+// so an end time this far past the probed duration is still accepted
+const TRIM_END_TOLERANCE_SECS: f64 = 0.1;
+
+// Check that [start, end] lies within the video: 0 <= start < end <= duration
+fn check_trim_bounds(input_path: &str, start_time: f64, end_time: f64) -> Result<(), ExportError> {
+    if !(start_time >= 0.0 && end_time > start_time) {
+        return Err(ExportError::InvalidInput {
+            message: format!("Invalid trim range: {:.3}s - {:.3}s", start_time, end_time),
+        });
+    }
+    let metadata = ffmpeg::read_video_metadata(input_path).map_err(probe_error)?;
+    if metadata.duration > 0.0 && end_time > metadata.duration + TRIM_END_TOLERANCE_SECS {
+        return Err(ExportError::InvalidInput {
+            message: format!("Trim end {:.3}s is past the end of the video ({:.3}s)", end_time, metadata.duration),
+        });
+    }
+    Ok(())
+}
+
+// EBU R128 loudness normalization to -16 LUFS, the usual target for voice
+// and online video. loudnorm upsamples to 192 kHz, so the output rate is
+// set back to 48 kHz alongside it. Single pass, applied after any fades;
+// segment joins measure the joined audio as a whole
+const LOUDNORM_FILTER: &str = "loudnorm=I=-16:TP=-1.5:LRA=11";
+const NORMALIZED_SAMPLE_RATE: &str = "48000";
+
+// Whether to normalize a clip's audio: only when asked for and the clip has
+// an audio stream to normalize
+fn wants_loudnorm(input_path: &str, normalize_audio: bool) -> Result<bool, ExportError> {
+    if !normalize_audio {
+        return Ok(false);
+    }
+    ffmpeg::has_audio_stream(input_path).map_err(probe_error)
+}
+
+// Emits "trim-progress" (0-1) while FFmpeg runs
 #[tauri::command]
 async fn trim_video(
+    app: tauri::AppHandle,
     input_path: String,
     output_path: String,
     start_time: f64,
     end_time: f64,
-) -> Result<String, String> {
+    precise: Option<bool>,
+    fade_in: Option<f64>,   // Seconds to fade in from black at the start
+    fade_out: Option<f64>,  // Seconds to fade out to black at the end
+    normalize_audio: Option<bool>,  // Bring the audio to -16 LUFS
+) -> Result<TrimResult, ExportError> {
+    trim_clip(
+        input_path, output_path, start_time, end_time, precise.unwrap_or(false),
+        fade_in, fade_out, normalize_audio.unwrap_or(false),
+        |fraction| {
+            app.emit("trim-progress", fraction).ok();
+        },
+    )
+}
+
+// Shared by trim_video and export_batch, which report progress differently
+fn trim_clip(
+    input_path: String,
+    output_path: String,
+    start_time: f64,
+    end_time: f64,
+    precise: bool,
+    fade_in: Option<f64>,
+    fade_out: Option<f64>,
+    normalize_audio: bool,
+    on_progress: impl Fn(f64),
+) -> Result<TrimResult, ExportError> {
+    check_trim_bounds(&input_path, start_time, end_time)?;
+    let loudnorm = wants_loudnorm(&input_path, normalize_audio)?;
     let duration = end_time - start_time;
+    let fade_in = fade_in.filter(|d| *d > 0.0);
+    let fade_out = fade_out.filter(|d| *d > 0.0);
+    // Fades are filters, which need decoded frames, so they re-encode too
+    let precise = precise || fade_in.is_some() || fade_out.is_some();
+    
+    let mut args: Vec<String> = vec![
+        "-y".to_string(),                               // Overwrite output
+        "-i".to_string(), input_path,                   // Input file
+        "-ss".to_string(), format!("{:.3}", start_time), // Start time
+        "-t".to_string(), format!("{:.3}", duration),    // Duration
+    ];
+    if precise {
+        // Fade times are relative to the trimmed output, which starts at 0
+        let mut video_filters: Vec<String> = Vec::new();
+        let mut audio_filters: Vec<String> = Vec::new();
+        if let Some(d) = fade_in {
+            video_filters.push(format!("fade=t=in:st=0:d={:.3}", d));
+            audio_filters.push(format!("afade=t=in:st=0:d={:.3}", d));
+        }
+        if let Some(d) = fade_out {
+            let start = (duration - d).max(0.0);
+            video_filters.push(format!("fade=t=out:st={:.3}:d={:.3}", start, d));
+            audio_filters.push(format!("afade=t=out:st={:.3}:d={:.3}", start, d));
+        }
+        if loudnorm {
+            audio_filters.push(LOUDNORM_FILTER.to_string());
+        }
+        if !video_filters.is_empty() {
+            args.extend(["-vf".to_string(), video_filters.join(",")]);
+        }
+        if !audio_filters.is_empty() {
+            args.extend(["-af".to_string(), audio_filters.join(",")]);
+        }
+        if loudnorm {
+            args.extend(["-ar".to_string(), NORMALIZED_SAMPLE_RATE.to_string()]);
+        }
+        
+        // Decode and re-encode both streams, so video and audio are cut at
+        // the same exact time and stay in sync
+        let (encoder, preset, crf) = get_encoding_params("medium", "mp4");
+        args.extend([
+            "-c:v".to_string(), encoder,
+            "-preset".to_string(), preset,
+            "-crf".to_string(), crf,
+            "-pix_fmt".to_string(), "yuv420p".to_string(),
+            "-c:a".to_string(), "aac".to_string(),
+            "-b:a".to_string(), "192k".to_string(),
+        ]);
+    } else if loudnorm {
+        // The video is still copied; only the audio goes through the filter
+        args.extend([
+            "-c:v".to_string(), "copy".to_string(),
+            "-af".to_string(), LOUDNORM_FILTER.to_string(),
+            "-ar".to_string(), NORMALIZED_SAMPLE_RATE.to_string(),
+            "-c:a".to_string(), "aac".to_string(),
+            "-b:a".to_string(), "192k".to_string(),
+        ]);
+    } else {
+        args.extend(["-c".to_string(), "copy".to_string()]);  // Copy codec (fast, no re-encoding)
+    }
+    args.push(output_path.clone());                     // Output file
     
     // Use FFmpeg to trim the video
-    let output = Command::new("ffmpeg")
-        .args([
-            "-y",                           // Overwrite output
-            "-i", &input_path,              // Input file
-            "-ss", &format!("{:.3}", start_time), // Start time
-            "-t", &format!("{:.3}", duration),    // Duration
-            "-c", "copy",                   // Copy codec (fast, no re-encoding)
-            &output_path,                   // Output file
-        ])
-        .output()
-        .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+    run_ffmpeg_with_progress(args, duration, on_progress)?;
+    Ok(TrimResult { output_path, frame_accurate: precise })
+}
 
-    if output.status.success() {
-        Ok(output_path)
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("FFmpeg failed: {}", stderr))
+// Keep several [start, end] ranges of a video and join them, cutting out
+// everything in between. Segments must be in order and must not overlap.
+// A single range is handed to trim_video as is; several are always
+// re-encoded, since stream copy only cuts at keyframes and the joins would
+// jump. Emits "trim-progress" like trim_video
+#[tauri::command]
+async fn trim_video_segments(
+    app: tauri::AppHandle,
+    input_path: String,
+    output_path: String,
+    keep_segments: Vec<(f64, f64)>,
+    normalize_audio: Option<bool>,  // Bring the audio to -16 LUFS
+) -> Result<TrimResult, ExportError> {
+    if keep_segments.is_empty() {
+        return Err(ExportError::InvalidInput { message: "No segments to keep".to_string() });
     }
+    for (i, (start, end)) in keep_segments.iter().enumerate() {
+        if *start < 0.0 || end <= start {
+            return Err(ExportError::InvalidInput {
+                message: format!("Segment {} is invalid: {:.3}s - {:.3}s", i + 1, start, end),
+            });
+        }
+        if let Some((next_start, _)) = keep_segments.get(i + 1) {
+            if next_start < end {
+                return Err(ExportError::InvalidInput {
+                    message: format!("Segment {} overlaps or comes after segment {}", i + 2, i + 1),
+                });
+            }
+        }
+    }
+    
+    // A single range is a plain trim
+    if let [(start, end)] = keep_segments[..] {
+        return trim_video(app, input_path, output_path, start, end, None, None, None, normalize_audio).await;
+    }
+    
+    if let Some((_, last_end)) = keep_segments.last() {
+        check_trim_bounds(&input_path, keep_segments[0].0, *last_end)?;
+    }
+    
+    // Cut each range out of the decoded streams, then concatenate them.
+    // Recordings without audio only have a video chain
+    let has_audio = ffmpeg::has_audio_stream(&input_path).map_err(probe_error)?;
+    let loudnorm = has_audio && normalize_audio.unwrap_or(false);
+    let mut filters: Vec<String> = Vec::new();
+    let mut concat_inputs = String::new();
+    for (i, (start, end)) in keep_segments.iter().enumerate() {
+        filters.push(format!("[0:v]trim=start={:.3}:end={:.3},setpts=PTS-STARTPTS[v{}]", start, end, i));
+        concat_inputs.push_str(&format!("[v{}]", i));
+        if has_audio {
+            filters.push(format!("[0:a]atrim=start={:.3}:end={:.3},asetpts=PTS-STARTPTS[a{}]", start, end, i));
+            concat_inputs.push_str(&format!("[a{}]", i));
+        }
+    }
+    let audio_streams = if has_audio { 1 } else { 0 };
+    // Normalize after joining, so the whole output is measured as one
+    let audio_out = match (has_audio, loudnorm) {
+        (true, true) => "[joineda]",
+        (true, false) => "[outa]",
+        (false, _) => "",
+    };
+    filters.push(format!(
+        "{}concat=n={}:v=1:a={}[outv]{}",
+        concat_inputs, keep_segments.len(), audio_streams, audio_out
+    ));
+    if loudnorm {
+        filters.push(format!("[joineda]{}[outa]", LOUDNORM_FILTER));
+    }
+    
+    let (encoder, preset, crf) = get_encoding_params("medium", "mp4");
+    let mut args: Vec<String> = vec![
+        "-y".to_string(),
+        "-i".to_string(), input_path,
+        "-filter_complex".to_string(), filters.join(";"),
+        "-map".to_string(), "[outv]".to_string(),
+    ];
+    if has_audio {
+        args.extend([
+            "-map".to_string(), "[outa]".to_string(),
+            "-c:a".to_string(), "aac".to_string(),
+            "-b:a".to_string(), "192k".to_string(),
+        ]);
+        if loudnorm {
+            args.extend(["-ar".to_string(), NORMALIZED_SAMPLE_RATE.to_string()]);
+        }
+    }
+    args.extend([
+        "-c:v".to_string(), encoder,
+        "-preset".to_string(), preset,
+        "-crf".to_string(), crf,
+        "-pix_fmt".to_string(), "yuv420p".to_string(),
+        output_path.clone(),
+    ]);
+    
+    println!("Joining {} segments into {}", keep_segments.len(), output_path);
+    
+    let kept_duration: f64 = keep_segments.iter().map(|(start, end)| end - start).sum();
+    run_ffmpeg_with_progress(args, kept_duration, |fraction| {
+        app.emit("trim-progress", fraction).ok();
+    })?;
+    Ok(TrimResult { output_path, frame_accurate: true })
+}
+
+// One clip of a batch export. Effects are drawn by the editor's canvas
+// renderer, so a batch only trims (and optionally fades) each clip
+#[derive(serde::Deserialize)]
+struct BatchClip {
+    input_path: String,
+    output_path: String,
+    start_time: f64,
+    end_time: f64,
+    precise: Option<bool>,
+    fade_in: Option<f64>,
+    fade_out: Option<f64>,
+    normalize_audio: Option<bool>,
+}
+
+// Payload of "batch-progress"
+#[derive(serde::Serialize, Clone)]
+struct BatchProgress {
+    index: usize,          // Clip being exported (0-based)
+    total: usize,
+    clip_progress: f64,    // 0-1 within the current clip
+}
+
+#[derive(serde::Serialize)]
+struct BatchClipResult {
+    input_path: String,
+    result: Option<TrimResult>,
+    error: Option<ExportError>,
+}
+
+// Export clips one after another, emitting "batch-progress". Stops at the
+// first failure unless continue_on_error is set; clips that never ran are
+// left out of the results
+#[tauri::command]
+async fn export_batch(
+    app: tauri::AppHandle,
+    clips: Vec<BatchClip>,
+    continue_on_error: Option<bool>,
+) -> Result<Vec<BatchClipResult>, ExportError> {
+    let continue_on_error = continue_on_error.unwrap_or(false);
+    let total = clips.len();
+    let mut results = Vec::with_capacity(total);
+    
+    for (index, clip) in clips.into_iter().enumerate() {
+        println!("Batch export {}/{}: {}", index + 1, total, clip.input_path);
+        let input_path = clip.input_path.clone();
+        let outcome = trim_clip(
+            clip.input_path,
+            clip.output_path,
+            clip.start_time,
+            clip.end_time,
+            clip.precise.unwrap_or(false),
+            clip.fade_in,
+            clip.fade_out,
+            clip.normalize_audio.unwrap_or(false),
+            |clip_progress| {
+                app.emit("batch-progress", BatchProgress { index, total, clip_progress }).ok();
+            },
+        );
+        
+        match outcome {
+            Ok(result) => results.push(BatchClipResult { input_path, result: Some(result), error: None }),
+            Err(error) => {
+                eprintln!("Batch export of {} failed: {:?}", input_path, error);
+                results.push(BatchClipResult { input_path, result: None, error: Some(error) });
+                if !continue_on_error {
+                    break;
+                }
+            }
+        }
+    }
+    
+    Ok(results)
 }
 
 // ============================================================================
 // ENCODING UTILITIES
 // ============================================================================
 
-// Get encoding parameters based on quality setting
-fn get_encoding_params(quality: &str) -> (String, String, String) {
+// Get encoding parameters based on quality setting and output container
+fn get_encoding_params(quality: &str, format: &str) -> (String, String, String) {
     // Returns (encoder, preset, crf/quality)
-    // GPU encoding disabled - always use software encoder (libx264)
+    if format == "webm" {
+        // VP9 has no x264-style presets; the "preset" is libvpx's -cpu-used speed
+        let (speed, crf) = match quality {
+            "high" => ("1", "24"),
+            "medium" => ("2", "31"),
+            "low" => ("4", "36"),
+            _ => ("2", "31"),
+        };
+        return ("libvpx-vp9".to_string(), speed.to_string(), crf.to_string());
+    }
+
+    // Software encoder (libx264) unless an export asks for a hardware one
     let (preset, crf) = match quality {
         "high" => ("slower", "16"),     // Best quality
         "medium" => ("medium", "20"),   // Balanced
@@ -96,6 +486,190 @@ fn get_encoding_params(quality: &str) -> (String, String, String) {
     ("libx264".to_string(), preset.to_string(), crf.to_string())
 }
 
+// Parse an FFmpeg-style bitrate ("2500k", "4M" or plain bits per second)
+fn parse_bitrate(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    let (number, multiplier) = match text.chars().last() {
+        Some('k') | Some('K') => (&text[..text.len() - 1], 1_000.0),
+        Some('m') | Some('M') => (&text[..text.len() - 1], 1_000_000.0),
+        _ => (text, 1.0),
+    };
+    match number.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() && value > 0.0 => Ok((value * multiplier) as u64),
+        _ => Err(format!("Invalid bitrate: {:?} (expected e.g. \"2500k\" or \"4M\")", text)),
+    }
+}
+
+// Lowest bitrate worth encoding at; below this the video is a smear
+const MIN_TARGET_BITRATE: u64 = 100_000;
+
+// Share of a size cap given to the video stream, leaving room for the
+// container overhead and the encoder overshooting its target
+const FILESIZE_BUDGET: f64 = 0.95;
+
+// Bits per second that fit a video of `duration` seconds into `max_mb` megabytes
+fn bitrate_for_filesize(max_mb: f64, duration: f64) -> u64 {
+    (max_mb * 1_000_000.0 * 8.0 * FILESIZE_BUDGET / duration) as u64
+}
+
+// Hardware H.264 encoders an export can ask for, by the name the frontend
+// uses ("software" picks libx264). Only encode_frames takes one; trims and
+// segment joins always use libx264
+const HARDWARE_ENCODERS: [(&str, &str); 3] = [("nvenc", "h264_nvenc"), ("qsv", "h264_qsv"), ("amf", "h264_amf")];
+
+// The hardware encoder to export with, or None for the software encoder.
+// A hardware encoder must be listed by `ffmpeg -encoders`, so a missing one
+// is reported up front instead of as an FFmpeg failure after rendering
+fn resolve_export_encoder(requested: Option<&str>, format: &str) -> Result<Option<&'static str>, ExportError> {
+    let requested = match requested {
+        None | Some("software") => return Ok(None),
+        Some(requested) => requested,
+    };
+    let encoder = HARDWARE_ENCODERS
+        .iter()
+        .find(|(name, _)| *name == requested)
+        .map(|(_, encoder)| *encoder)
+        .ok_or_else(|| ExportError::InvalidInput {
+            message: format!("Unknown encoder \"{}\": expected software, nvenc, qsv or amf", requested),
+        })?;
+    if format != "mp4" {
+        return Err(ExportError::InvalidInput {
+            message: format!("{} only encodes H.264; export as MP4 or use the software encoder", encoder),
+        });
+    }
+    if !ffmpeg::encoder_available(encoder) {
+        return Err(ExportError::InvalidInput {
+            message: format!("{} is not available in this FFmpeg build", encoder),
+        });
+    }
+    Ok(Some(encoder))
+}
+
+// FFmpeg rate control for an encoder: constant quality (CRF) by default, or
+// an average bitrate capped by -maxrate/-bufsize when a target is given.
+// Hardware encoders get the x264 preset translated to their own speed
+// setting (fast/medium/slow presets: NVENC p2/p4/p6, QSV veryfast/medium/
+// slower, AMF speed/balanced/quality) and the CRF as their constant-quality
+// value
+fn rate_control_args(encoder: &str, preset: String, crf: String, bitrate: Option<u64>) -> Vec<String> {
+    let speed = |fast: &str, medium: &str, slow: &str| match preset.as_str() {
+        "ultrafast" | "superfast" | "veryfast" | "faster" | "fast" => fast.to_string(),
+        "slow" | "slower" | "veryslow" => slow.to_string(),
+        _ => medium.to_string(),
+    };
+    let mut args = match encoder {
+        // -row-mt speeds VP9 up considerably
+        "libvpx-vp9" => vec![
+            "-cpu-used".to_string(), preset.clone(),
+            "-row-mt".to_string(), "1".to_string(),
+        ],
+        "h264_nvenc" => vec!["-preset".to_string(), speed("p2", "p4", "p6")],
+        "h264_qsv" => vec!["-preset".to_string(), speed("veryfast", "medium", "slower")],
+        "h264_amf" => vec!["-quality".to_string(), speed("speed", "balanced", "quality")],
+        _ => vec!["-preset".to_string(), preset.clone()],
+    };
+    match bitrate {
+        Some(bitrate) => args.extend([
+            "-b:v".to_string(), bitrate.to_string(),
+            "-maxrate".to_string(), bitrate.to_string(),
+            "-bufsize".to_string(), (bitrate * 2).to_string(),
+        ]),
+        // VP9 needs -b:v 0 for constant-quality mode
+        None if encoder == "libvpx-vp9" => args.extend([
+            "-b:v".to_string(), "0".to_string(),
+            "-crf".to_string(), crf,
+        ]),
+        None if encoder == "h264_nvenc" => args.extend([
+            "-rc".to_string(), "vbr".to_string(),
+            "-cq".to_string(), crf,
+            "-b:v".to_string(), "0".to_string(),
+        ]),
+        None if encoder == "h264_qsv" => args.extend(["-global_quality".to_string(), crf]),
+        None if encoder == "h264_amf" => args.extend([
+            "-rc".to_string(), "cqp".to_string(),
+            "-qp_i".to_string(), crf.clone(),
+            "-qp_p".to_string(), crf,
+        ]),
+        None => args.extend(["-crf".to_string(), crf]),
+    }
+    args
+}
+
+// Make the output path's extension match the container being written
+fn with_format_extension(path: &str, format: &str) -> String {
+    let path = std::path::Path::new(path);
+    if path.extension().map_or(false, |ext| ext.eq_ignore_ascii_case(format)) {
+        return path.to_string_lossy().to_string();
+    }
+    path.with_extension(format).to_string_lossy().to_string()
+}
+
+// Parse the `out_time=HH:MM:SS.micro` lines FFmpeg writes with `-progress`
+// Returns None for any other key, or when the time is not known yet ("N/A")
+fn parse_progress_seconds(line: &str) -> Option<f64> {
+    let value = line.trim().strip_prefix("out_time=")?;
+    let mut parts = value.split(':');
+    let hours: f64 = parts.next()?.parse().ok()?;
+    let minutes: f64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+// Run FFmpeg to completion, calling `on_progress` with the 0-1 fraction of
+// `duration` seconds of output written so far. The output path must be
+// the last argument
+fn run_ffmpeg_with_progress(
+    mut args: Vec<String>,
+    duration: f64,
+    on_progress: impl Fn(f64),
+) -> Result<(), ExportError> {
+    let output_index = args.len().saturating_sub(1);
+    args.splice(output_index..output_index, [
+        "-progress".to_string(), "pipe:1".to_string(),
+        "-nostats".to_string(),
+    ]);
+    
+    let mut child = Command::new(ffmpeg::ffmpeg_path())
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(ffmpeg_launch_error)?;
+    
+    // Drain stderr on its own thread so FFmpeg never blocks on a full pipe
+    let stderr_pipe = child.stderr.take();
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buf = String::new();
+        if let Some(mut pipe) = stderr_pipe {
+            pipe.read_to_string(&mut buf).ok();
+        }
+        buf
+    });
+    
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if let Some(seconds) = parse_progress_seconds(&line) {
+                if duration > 0.0 {
+                    on_progress((seconds / duration).clamp(0.0, 1.0));
+                }
+            }
+        }
+    }
+    
+    let status = child.wait()
+        .map_err(|e| format!("Failed to wait for FFmpeg: {}", e))?;
+    let stderr = stderr_reader.join().unwrap_or_default();
+    if status.success() {
+        on_progress(1.0);
+        Ok(())
+    } else {
+        Err(ExportError::EncodeFailed {
+            summary: ffmpeg::error_summary(&stderr),
+            debug: stderr,
+        })
+    }
+}
+
 // ============================================================================
 // CANVAS-BASED EXPORT: Encode pre-rendered RGB frames
 // ============================================================================
@@ -105,26 +679,241 @@ fn get_encoding_params(quality: &str) -> (String, String, String) {
 // and encode them with FFmpeg.
 //
 // Frame Format: RGB24 (3 bytes per pixel: R, G, B)
-// Input: Raw frame bytes sent from JS via Tauri command (base64 encoded)
+// Input: Raw frame bytes sent from JS via Tauri command (base64 encoded),
+//        plus the source recording for its audio
 // Output: Encoded video file
 
+// Soundtrack of a canvas export: the audio of the source recording over the
+// exported range. The frames only carry video, so without it the export
+// would be silent
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportAudio {
+    source_path: String,
+    start_time: f64,  // Range of the source the frames were rendered from
+    end_time: f64,
+    speed: Option<f64>,     // Playback speed of the frames (2 = twice as fast)
+    fade_in: Option<f64>,   // Seconds to fade in from silence, matching the frames' fade
+    fade_out: Option<f64>,  // Seconds to fade out to silence at the end
+    normalize: Option<bool>,  // Bring the audio to -16 LUFS
+}
+
+// atempo filters for a speed change. A single atempo only takes factors
+// from 0.5 to 2 in older FFmpeg builds, so larger changes are chained
+// (3x = 2x then 1.5x)
+fn atempo_filters(speed: f64) -> Vec<String> {
+    let mut filters = Vec::new();
+    let mut remaining = speed;
+    while remaining > 2.0 {
+        filters.push("atempo=2.0".to_string());
+        remaining /= 2.0;
+    }
+    while remaining < 0.5 {
+        filters.push("atempo=0.5".to_string());
+        remaining /= 0.5;
+    }
+    if (remaining - 1.0).abs() > 1e-6 {
+        filters.push(format!("atempo={:.6}", remaining));
+    }
+    filters
+}
+
+// Audio filters for a canvas export lasting `duration` seconds. The speed
+// change comes first, so the fades are in output time like the rendered
+// fades; loudness is measured last, on the audio as it will be heard
+fn export_audio_filters(audio: &ExportAudio, duration: f64) -> Vec<String> {
+    let mut filters = atempo_filters(audio.speed.unwrap_or(1.0));
+    if let Some(d) = audio.fade_in.filter(|d| *d > 0.0) {
+        filters.push(format!("afade=t=in:st=0:d={:.3}", d));
+    }
+    if let Some(d) = audio.fade_out.filter(|d| *d > 0.0) {
+        filters.push(format!("afade=t=out:st={:.3}:d={:.3}", (duration - d).max(0.0), d));
+    }
+    if audio.normalize.unwrap_or(false) {
+        filters.push(LOUDNORM_FILTER.to_string());
+    }
+    filters
+}
+
+// A canvas export in progress
+struct RunningExport {
+    cancel_requested: Arc<AtomicBool>,
+    ffmpeg_process: Option<std::process::Child>,  // Running encoder, once started
+}
+
+// Exports in progress, keyed by the id encode_frames returns, so one can be
+// cancelled from the frontend without touching the others
+pub struct ExportState {
+    next_id: AtomicU64,
+    exports: Mutex<HashMap<u64, RunningExport>>,
+}
+
+impl ExportState {
+    pub fn new() -> Self {
+        Self {
+            next_id: AtomicU64::new(1),
+            exports: Mutex::new(HashMap::new()),
+        }
+    }
+    
+    // Add an export, returning its id and cancel flag
+    fn register(&self) -> (u64, Arc<AtomicBool>) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let cancel_requested = Arc::new(AtomicBool::new(false));
+        self.exports.lock().unwrap().insert(id, RunningExport {
+            cancel_requested: cancel_requested.clone(),
+            ffmpeg_process: None,
+        });
+        (id, cancel_requested)
+    }
+    
+    fn set_process(&self, id: u64, child: std::process::Child) {
+        if let Some(export) = self.exports.lock().unwrap().get_mut(&id) {
+            export.ffmpeg_process = Some(child);
+        }
+    }
+    
+    fn take_process(&self, id: u64) -> Option<std::process::Child> {
+        self.exports.lock().unwrap().get_mut(&id).and_then(|export| export.ffmpeg_process.take())
+    }
+    
+    fn finish(&self, id: u64) {
+        self.exports.lock().unwrap().remove(&id);
+    }
+}
+
+// Payload of "export-finished": exactly one of output_path and error is set
+#[derive(serde::Serialize, Clone)]
+struct ExportFinished {
+    id: u64,
+    output_path: Option<String>,
+    error: Option<ExportError>,
+}
+
+// Why an export failed, serialized as { kind: "encodeFailed", summary, debug }
+// etc. so the frontend can show a short message instead of FFmpeg's full log
+#[derive(serde::Serialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum ExportError {
+    FfmpegNotFound,
+    Cancelled,
+    InvalidInput { message: String },
+    ProbeFailed { message: String },
+    InsufficientDiskSpace { path: String, needed_bytes: u64, free_bytes: u64 },
+    Io { message: String },
+    EncodeFailed { summary: String, debug: String },  // debug holds the full stderr
+}
+
+// A missing binary gets its own variant so the UI can ask to install FFmpeg
+fn ffmpeg_launch_error(e: std::io::Error) -> ExportError {
+    match e.kind() {
+        std::io::ErrorKind::NotFound => ExportError::FfmpegNotFound,
+        _ => ExportError::Io { message: format!("Failed to run FFmpeg: {}", e) },
+    }
+}
+
+// ffprobe ships with FFmpeg, so a missing one is reported the same way
+fn probe_error(e: ffmpeg::ProbeError) -> ExportError {
+    match e {
+        ffmpeg::ProbeError::NotFound => ExportError::FfmpegNotFound,
+        ffmpeg::ProbeError::Failed(message) => ExportError::ProbeFailed { message },
+    }
+}
+
+// Plain string errors come from file handling (temp frames, pipes)
+impl From<String> for ExportError {
+    fn from(message: String) -> Self {
+        ExportError::Io { message }
+    }
+}
+
+// Stop the export with this id; it then finishes with a Cancelled error.
+// Unknown ids (already finished exports) are ignored
+#[tauri::command]
+fn cancel_export(state: tauri::State<'_, ExportState>, id: u64) {
+    if let Some(export) = state.exports.lock().unwrap().get_mut(&id) {
+        export.cancel_requested.store(true, Ordering::Relaxed);
+        // Kill FFmpeg right away rather than waiting for its next progress line
+        if let Some(child) = export.ffmpeg_process.as_mut() {
+            child.kill().ok();
+        }
+    }
+}
+
+// Everything encode_frames was called with, handed to the export thread
+struct CanvasExport {
+    output_path: String,
+    width: i32,
+    height: i32,
+    fps: i32,
+    quality: Option<String>,
+    format: Option<String>,
+    target_bitrate: Option<String>,
+    max_filesize_mb: Option<f64>,
+    encoder: Option<String>,
+    audio: Option<ExportAudio>,
+    frames_base64: Vec<String>,
+}
+
 /// Encode raw RGB frames into a video file
 /// 
 /// This command receives all frame data at once and encodes to a video.
 /// For large videos, we may need to stream frames, but this works for most cases.
+/// Returns an export id right away and encodes in the background: progress
+/// is emitted as `export-progress` events (0.0 - 1.0) and the outcome as
+/// `export-finished` with the same id. cancel_export(id) stops it.
 #[tauri::command]
 async fn encode_frames(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, ExportState>,
     output_path: String,
     width: i32,
     height: i32,
     fps: i32,
     quality: Option<String>,
+    format: Option<String>,      // "mp4" (default) or "webm"
+    target_bitrate: Option<String>,   // e.g. "4M"; replaces the quality's CRF
+    max_filesize_mb: Option<f64>,     // Size cap; the bitrate is derived from the duration
+    encoder: Option<String>,     // "software" (default), "nvenc", "qsv" or "amf"
+    audio: Option<ExportAudio>,  // Source audio to mux in; None exports video only
     frames_base64: Vec<String>,  // Base64 encoded RGB frames
-) -> Result<String, String> {
+) -> Result<u64, ExportError> {
+    let (id, cancel) = state.register();
+    let export = CanvasExport {
+        output_path, width, height, fps, quality, format,
+        target_bitrate, max_filesize_mb, encoder, audio, frames_base64,
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = run_canvas_export(&app, id, &cancel, export);
+        app.state::<ExportState>().finish(id);
+        let finished = match result {
+            Ok(output_path) => ExportFinished { id, output_path: Some(output_path), error: None },
+            Err(error) => ExportFinished { id, output_path: None, error: Some(error) },
+        };
+        app.emit("export-finished", finished).ok();
+    });
+    Ok(id)
+}
+
+fn run_canvas_export(
+    app: &tauri::AppHandle,
+    id: u64,
+    cancel: &AtomicBool,
+    export: CanvasExport,
+) -> Result<String, ExportError> {
     use std::io::Write;
     use base64::Engine;
     
+    let CanvasExport {
+        output_path, width, height, fps, quality, format,
+        target_bitrate, max_filesize_mb, encoder, audio, frames_base64,
+    } = export;
     let quality_setting = quality.unwrap_or_else(|| "high".to_string());
+    let format_setting = format.unwrap_or_else(|| "mp4".to_string());
+    if format_setting != "mp4" && format_setting != "webm" {
+        return Err(ExportError::InvalidInput { message: format!("Unsupported export format: {}", format_setting) });
+    }
+    let output_path = with_format_extension(&output_path, &format_setting);
     let frame_count = frames_base64.len();
     
     println!("=== ENCODE FRAMES (Canvas-Based Export) ===");
@@ -132,13 +921,80 @@ async fn encode_frames(
     println!("Resolution: {}x{} @ {}fps", width, height, fps);
     println!("Frames: {}", frame_count);
     println!("Quality: {}", quality_setting);
+    println!("Format: {}", format_setting);
     
     if frame_count == 0 {
-        return Err("No frames to encode".to_string());
+        return Err(ExportError::InvalidInput { message: "No frames to encode".to_string() });
+    }
+    let hardware_encoder = resolve_export_encoder(encoder.as_deref(), &format_setting)?;
+    if let Some(speed) = audio.as_ref().and_then(|audio| audio.speed) {
+        if !speed.is_finite() || speed <= 0.0 {
+            return Err(ExportError::InvalidInput { message: format!("Invalid playback speed: {}", speed) });
+        }
+    }
+    // Recordings without a microphone or system audio have nothing to mux
+    let audio = match audio {
+        Some(audio) if ffmpeg::has_audio_stream(&audio.source_path).map_err(probe_error)? => Some(audio),
+        _ => None,
+    };
+    
+    // Bitrate targeting, CRF when neither a bitrate nor a size is given
+    let duration = frame_count as f64 / fps as f64;
+    let bitrate = match (&target_bitrate, max_filesize_mb) {
+        (Some(_), Some(_)) => {
+            return Err(ExportError::InvalidInput {
+                message: "Set either a target bitrate or a maximum file size, not both".to_string(),
+            });
+        }
+        (Some(text), None) => Some(parse_bitrate(text).map_err(|message| ExportError::InvalidInput { message })?),
+        (None, Some(max_mb)) if max_mb.is_finite() && max_mb > 0.0 => Some(bitrate_for_filesize(max_mb, duration)),
+        (None, Some(max_mb)) => {
+            return Err(ExportError::InvalidInput { message: format!("Invalid maximum file size: {} MB", max_mb) });
+        }
+        (None, None) => None,
+    };
+    if let Some(bitrate) = bitrate {
+        if bitrate < MIN_TARGET_BITRATE {
+            let message = if target_bitrate.is_some() {
+                format!(
+                    "Target bitrate of {} kbps is below the minimum of {} kbps",
+                    bitrate / 1000, MIN_TARGET_BITRATE / 1000
+                )
+            } else {
+                format!(
+                    "{} kbps is too low for {}x{} video; allow a larger file or export a shorter clip",
+                    bitrate / 1000, width, height
+                )
+            };
+            return Err(ExportError::InvalidInput { message });
+        }
+        println!("Target bitrate: {} kbps", bitrate / 1000);
+    }
+    
+    // Check both drives before writing anything: the raw frames go to the
+    // temp directory uncompressed, the encoded video next to output_path
+    // One directory per export, so concurrent exports never share frames
+    let temp_dir = std::env::temp_dir().join(format!("visualcoder_frames_{}_{}", std::process::id(), id));
+    let raw_bytes = frame_count as u64 * width as u64 * height as u64 * 3;
+    let encoded_bytes = bitrate.map(|bitrate| bitrate / 8)
+        .unwrap_or_else(|| recorder::estimated_bytes_per_second(width as u32, height as u32, fps as f64))
+        * (frame_count as u64).div_ceil(fps.max(1) as u64);
+    for (dir, needed_bytes) in [(std::env::temp_dir(), raw_bytes), (recorder::output_dir(&output_path), encoded_bytes)] {
+        match recorder::free_disk_space(&dir) {
+            Ok(free_bytes) if free_bytes < needed_bytes => {
+                return Err(ExportError::InsufficientDiskSpace {
+                    path: dir.to_string_lossy().to_string(),
+                    needed_bytes,
+                    free_bytes,
+                });
+            }
+            Ok(_) => {}
+            // Not being able to check shouldn't block the export
+            Err(e) => eprintln!("{}", e),
+        }
     }
     
     // Create temp directory
-    let temp_dir = std::env::temp_dir().join(format!("visualcoder_frames_{}", std::process::id()));
     std::fs::create_dir_all(&temp_dir)
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
     
@@ -152,11 +1008,27 @@ async fn encode_frames(
         .map_err(|e| format!("Failed to create raw video file: {}", e))?;
     
     println!("Decoding and writing {} frames to raw file...", frame_count);
+    let frame_bytes = width as usize * height as usize * 3;
     
     for (i, frame_b64) in frames_base64.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            drop(raw_file);
+            std::fs::remove_dir_all(&temp_dir).ok();
+            return Err(ExportError::Cancelled);
+        }
+        
         let frame_data = base64::engine::general_purpose::STANDARD
             .decode(frame_b64)
             .map_err(|e| format!("Failed to decode frame {}: {}", i, e))?;
+        // A frame of the wrong size would shift every following frame in the
+        // raw stream and scramble the rest of the video
+        if frame_data.len() != frame_bytes {
+            drop(raw_file);
+            std::fs::remove_dir_all(&temp_dir).ok();
+            return Err(ExportError::InvalidInput {
+                message: format!("Frame {} is {} bytes, expected {} for {}x{} RGB", i, frame_data.len(), frame_bytes, width, height),
+            });
+        }
         
         raw_file.write_all(&frame_data)
             .map_err(|e| format!("Failed to write frame {}: {}", i, e))?;
@@ -173,62 +1045,190 @@ async fn encode_frames(
     println!("Wrote all frames to {:?}", raw_video_path);
     
     // Build FFmpeg command for encoding raw RGB frames
-    let (encoder, preset, crf) = get_encoding_params(&quality_setting);
+    let (software_encoder, preset, crf) = get_encoding_params(&quality_setting, &format_setting);
+    let encoder = hardware_encoder.map(str::to_string).unwrap_or(software_encoder);
+    println!("Encoder: {}", encoder);
+    // QSV only takes NV12 input; the others convert from yuv420p themselves
+    let pix_fmt = if encoder == "h264_qsv" { "nv12" } else { "yuv420p" };
     
     let raw_path_str = raw_video_path.to_string_lossy();
     
-    let args = vec![
+    let mut args = vec![
         "-y".to_string(),
         "-f".to_string(), "rawvideo".to_string(),
         "-pixel_format".to_string(), "rgb24".to_string(),
         "-video_size".to_string(), format!("{}x{}", width, height),
         "-framerate".to_string(), fps.to_string(),
         "-i".to_string(), raw_path_str.to_string(),
-        "-c:v".to_string(), encoder,
-        "-preset".to_string(), preset,
-        "-crf".to_string(), crf,
-        "-pix_fmt".to_string(), "yuv420p".to_string(),
-        output_path.clone(),
     ];
+    if let Some(audio) = &audio {
+        // Seek the source before opening it, so only the exported range is
+        // decoded; -t on the output keeps the audio from outlasting the frames
+        args.extend([
+            "-ss".to_string(), format!("{:.3}", audio.start_time),
+            "-t".to_string(), format!("{:.3}", audio.end_time - audio.start_time),
+            "-i".to_string(), audio.source_path.clone(),
+            "-map".to_string(), "0:v".to_string(),
+            "-map".to_string(), "1:a:0".to_string(),
+            "-t".to_string(), format!("{:.3}", duration),
+        ]);
+        let audio_filters = export_audio_filters(audio, duration);
+        if !audio_filters.is_empty() {
+            args.extend(["-af".to_string(), audio_filters.join(",")]);
+        }
+        if audio.normalize.unwrap_or(false) {
+            args.extend(["-ar".to_string(), NORMALIZED_SAMPLE_RATE.to_string()]);
+        }
+        // WebM only carries Opus or Vorbis audio
+        let (audio_codec, audio_bitrate) = if format_setting == "webm" { ("libopus", "128k") } else { ("aac", "192k") };
+        args.extend([
+            "-c:a".to_string(), audio_codec.to_string(),
+            "-b:a".to_string(), audio_bitrate.to_string(),
+        ]);
+    }
+    args.extend(["-c:v".to_string(), encoder.clone()]);
+    args.extend(rate_control_args(&encoder, preset, crf, bitrate));
+    args.extend([
+        "-pix_fmt".to_string(), pix_fmt.to_string(),
+        "-progress".to_string(), "pipe:1".to_string(),
+        "-nostats".to_string(),
+        output_path.clone(),
+    ]);
     
     println!("Running FFmpeg with args: {:?}", args);
     
-    let output = Command::new("ffmpeg")
+    let mut child = Command::new(ffmpeg::ffmpeg_path())
         .args(&args)
-        .output()
-        .map_err(|e| format!("Failed to execute FFmpeg: {}", e))?;
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(ffmpeg_launch_error)?;
+    
+    // Drain stderr on its own thread so FFmpeg never blocks on a full pipe
+    let stderr_pipe = child.stderr.take();
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buf = String::new();
+        if let Some(mut pipe) = stderr_pipe {
+            pipe.read_to_string(&mut buf).ok();
+        }
+        buf
+    });
+    
+    // Hand the process to ExportState so cancel_export can kill it
+    let stdout_pipe = child.stdout.take();
+    let state = app.state::<ExportState>();
+    state.set_process(id, child);
+    
+    // Progress lines arrive on stdout as key=value pairs
+    if let Some(stdout) = stdout_pipe {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            if let Some(seconds) = parse_progress_seconds(&line) {
+                if duration > 0.0 {
+                    let fraction = (seconds / duration).clamp(0.0, 1.0);
+                    app.emit("export-progress", fraction).ok();
+                }
+            }
+        }
+    }
+    
+    let mut child = state.take_process(id)
+        .ok_or_else(|| "FFmpeg process handle was lost".to_string())?;
+    
+    if cancel.load(Ordering::Relaxed) {
+        println!("Export cancelled, stopping FFmpeg");
+        child.kill().ok();
+        child.wait().ok();
+        stderr_reader.join().ok();
+        std::fs::remove_dir_all(&temp_dir).ok();
+        // Don't leave a truncated video behind
+        std::fs::remove_file(&output_path).ok();
+        return Err(ExportError::Cancelled);
+    }
+    
+    let status = child.wait()
+        .map_err(|e| format!("Failed to wait for FFmpeg: {}", e))?;
+    let stderr = stderr_reader.join().unwrap_or_default();
     
     // Cleanup temp directory
     if let Err(e) = std::fs::remove_dir_all(&temp_dir) {
         println!("Warning: Failed to cleanup temp directory: {}", e);
     }
     
-    if output.status.success() {
+    if status.success() {
+        app.emit("export-progress", 1.0).ok();
         println!("Encode successful! File saved to: {}", output_path);
         Ok(output_path)
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
         println!("FFmpeg failed: {}", stderr);
-        Err(format!("FFmpeg encoding failed: {}", stderr))
+        Err(ExportError::EncodeFailed {
+            summary: ffmpeg::error_summary(&stderr),
+            debug: stderr,
+        })
     }
 }
 
+// Save one rendered frame (RGB, like encode_frames receives) as a PNG
+#[tauri::command]
+async fn export_frame(
+    output_path: String,
+    width: u32,
+    height: u32,
+    frame_base64: String,
+) -> Result<String, String> {
+    use base64::Engine;
+    
+    let output_path = with_format_extension(&output_path, "png");
+    let frame_data = base64::engine::general_purpose::STANDARD
+        .decode(&frame_base64)
+        .map_err(|e| format!("Failed to decode frame: {}", e))?;
+    let expected_len = width as usize * height as usize * 3;
+    if frame_data.len() != expected_len {
+        return Err(format!("Frame is {} bytes, expected {} for {}x{} RGB", frame_data.len(), expected_len, width, height));
+    }
+    
+    image::save_buffer_with_format(&output_path, &frame_data, width, height, image::ColorType::Rgb8, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to save frame: {}", e))?;
+    println!("Frame saved to: {}", output_path);
+    Ok(output_path)
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .manage(RecorderState::new())
+        .manage(ExportState::new())
         .plugin(tauri_plugin_opener::init())
         .invoke_handler(tauri::generate_handler![
             greet,
+            ffmpeg::check_dependencies,
+            ffmpeg::probe_video,
+            ffmpeg::refresh_encoders,
+            ffmpeg::extract_thumbnail,
+            ffmpeg::extract_thumbnails,
             recorder::start_recording,
             recorder::stop_recording,
+            recorder::pause_recording,
+            recorder::resume_recording,
+            recorder::set_zoom_factor,
             recorder::get_open_windows,
+            recorder::get_monitors,
+            recorder::get_audio_devices,
+            recorder::get_video_devices,
             recorder::get_recorded_clicks,
             recorder::get_cursor_positions,
+            recorder::get_recording_stats,
+            recorder::detect_idle_ranges,
             trim_video,
+            trim_video_segments,
+            export_batch,
             encode_frames,
+            export_frame,
+            cancel_export,
             get_temp_video_path,
+            get_free_space,
             get_videos_dir_path,
             move_video_to_videos,
             delete_temp_video
@@ -237,3 +1237,79 @@ pub fn run() {
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_bitrate_reads_suffixes() {
+        let cases = [
+            ("2500k", 2_500_000),
+            ("2500K", 2_500_000),
+            ("4M", 4_000_000),
+            ("1.5m", 1_500_000),
+            (" 800000 ", 800_000),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse_bitrate(text), Ok(expected), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn parse_bitrate_rejects_junk() {
+        for text in ["", "k", "M", "fast", "4G", "0", "-2M", "inf", "NaN"] {
+            assert!(parse_bitrate(text).is_err(), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn parse_bitrate_leaves_the_minimum_to_the_export() {
+        // Small values parse; encode_frames rejects them against MIN_TARGET_BITRATE
+        assert!(parse_bitrate("50k").unwrap() < MIN_TARGET_BITRATE);
+        assert!(parse_bitrate("100k").unwrap() >= MIN_TARGET_BITRATE);
+    }
+
+    #[test]
+    fn bitrate_for_filesize_fits_the_budget() {
+        // 10 MB over 80 s is 1 Mbps, less the container allowance
+        assert_eq!(bitrate_for_filesize(10.0, 80.0), 950_000);
+        assert_eq!(bitrate_for_filesize(25.0, 10.0), 19_000_000);
+    }
+
+    #[test]
+    fn parse_progress_seconds_reads_out_time() {
+        let cases = [
+            ("out_time=00:00:00.000000", Some(0.0)),
+            ("out_time=00:01:02.500000", Some(62.5)),
+            ("out_time=01:00:00.000000\n", Some(3600.0)),
+            ("out_time=N/A", None),
+            ("out_time_ms=62500000", None),
+            ("frame=120", None),
+            ("", None),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(parse_progress_seconds(line), expected, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn check_trim_bounds_rejects_invalid_ranges() {
+        // Invalid ranges are caught before the file is probed
+        for (start, end) in [(-1.0, 5.0), (5.0, 5.0), (5.0, 2.0), (f64::NAN, 1.0), (0.0, f64::NAN)] {
+            assert!(
+                matches!(check_trim_bounds("missing.mp4", start, end), Err(ExportError::InvalidInput { .. })),
+                "{} - {}", start, end
+            );
+        }
+    }
+
+    #[test]
+    fn atempo_filters_chain_large_speed_changes() {
+        assert!(atempo_filters(1.0).is_empty());
+        assert_eq!(atempo_filters(1.5), vec!["atempo=1.500000"]);
+        assert_eq!(atempo_filters(2.0), vec!["atempo=2.000000"]);
+        assert_eq!(atempo_filters(3.0), vec!["atempo=2.0", "atempo=1.500000"]);
+        assert_eq!(atempo_filters(0.25), vec!["atempo=0.5", "atempo=0.500000"]);
+    }
+}
@@ -1,11 +1,12 @@
 use std::process::{Command, Stdio};
 use std::io::Write;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Mutex;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Condvar, Mutex};
 use std::thread;
-use std::time::Instant;
-use tauri::State;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
 
 use windows_capture::{
     capture::{Context, GraphicsCaptureApiHandler},
@@ -25,8 +26,10 @@ pub struct ClickEvent {
     pub timestamp_ms: u64,      // Time since recording start
     pub x: f64,                 // Normalized X (0.0 - 1.0)
     pub y: f64,                 // Normalized Y (0.0 - 1.0)
+    pub button: String,         // "left", "right" or "middle"
     pub is_double_click: bool,  // True if this was a double-click
     pub is_triple_click: bool,  // True if this was a triple-click (zoom trigger)
+    pub zoom_scale: Option<f64>,  // Zoom factor in effect for a triple-click, None for other clicks
 }
 
 // Cursor position captured during recording (for cursor-following zoom)
@@ -42,27 +45,65 @@ lazy_static::lazy_static! {
     static ref CLICK_EVENTS: Mutex<Vec<ClickEvent>> = Mutex::new(Vec::new());
     static ref CURSOR_POSITIONS: Mutex<Vec<CursorPosition>> = Mutex::new(Vec::new());
     static ref RECORDING_START_TIME: Mutex<Option<Instant>> = Mutex::new(None);
+    // When the current pause began (None while not paused)
+    static ref PAUSE_STARTED: Mutex<Option<Instant>> = Mutex::new(None);
     static ref LAST_CLICK: Mutex<Option<(Instant, f64, f64)>> = Mutex::new(None);
     // Track last two clicks for triple-click detection: [(time, x, y), (time, x, y)]
     static ref LAST_TWO_CLICKS: Mutex<Vec<(Instant, f64, f64)>> = Mutex::new(Vec::new());
     // Track when the last zoom was triggered to prevent stacking during active zoom
     static ref LAST_ZOOM_TRIGGER: Mutex<Option<Instant>> = Mutex::new(None);
-    static ref LAST_CURSOR_SAMPLE: Mutex<Option<Instant>> = Mutex::new(None);
     static ref SCREEN_SIZE: Mutex<(u32, u32)> = Mutex::new((1920, 1080));
     // Capture target bounds: (x, y, width, height) - for coordinate transformation
     // Screen capture: (0, 0, screen_w, screen_h)
     // Window capture: (window_left, window_top, window_w, window_h)
     static ref CAPTURE_BOUNDS: Mutex<(i32, i32, u32, u32)> = Mutex::new((0, 0, 1920, 1080));
+    // Frame counts of the last finished recording
+    static ref LAST_RECORDING_STATS: Mutex<Option<RecordingStats>> = Mutex::new(None);
+}
+
+// Longest gap between the clicks of a double or triple click. Matches the
+// triple-click zoom trigger's timing (two 400ms gaps). Double-clicks used
+// to allow 500ms, so slower ones now need a higher multi_click_ms; the
+// triple-click window is twice the threshold
+const DEFAULT_MULTI_CLICK_MS: u64 = 400;
+const MULTI_CLICK_MS_RANGE: std::ops::RangeInclusive<u64> = 100..=1500;
+
+// Zoom factor of triple-click zooms. Below 1x the zoomed view would be
+// larger than the frame, so factors start above it
+const DEFAULT_ZOOM_FACTOR: f64 = 1.5;
+const ZOOM_FACTOR_RANGE: std::ops::RangeInclusive<f64> = 1.1..=4.0;
+
+fn check_zoom_factor(factor: f64) -> Result<f64, String> {
+    if ZOOM_FACTOR_RANGE.contains(&factor) {
+        Ok(factor)
+    } else {
+        Err(format!(
+            "Zoom factor must be between {}x and {}x, got {}",
+            ZOOM_FACTOR_RANGE.start(), ZOOM_FACTOR_RANGE.end(), factor
+        ))
+    }
 }
 
 pub struct RecorderState {
     pub is_recording: Arc<AtomicBool>,
+    pub is_paused: Arc<AtomicBool>,
+    pub is_capturing_audio: Arc<AtomicBool>,
+    pub is_capturing_webcam: Arc<AtomicBool>,
+    pub is_counting_down: Arc<AtomicBool>,
+    pub multi_click_ms: Arc<AtomicU64>,  // Double/triple-click threshold of the current recording
+    pub zoom_factor: Arc<Mutex<f64>>,    // Zoom of triple-clicks, changeable while recording
 }
 
 impl RecorderState {
     pub fn new() -> Self {
         Self {
             is_recording: Arc::new(AtomicBool::new(false)),
+            is_paused: Arc::new(AtomicBool::new(false)),
+            is_capturing_audio: Arc::new(AtomicBool::new(false)),
+            is_capturing_webcam: Arc::new(AtomicBool::new(false)),
+            is_counting_down: Arc::new(AtomicBool::new(false)),
+            multi_click_ms: Arc::new(AtomicU64::new(DEFAULT_MULTI_CLICK_MS)),
+            zoom_factor: Arc::new(Mutex::new(DEFAULT_ZOOM_FACTOR)),
         }
     }
 }
@@ -71,21 +112,490 @@ impl RecorderState {
 struct CaptureFlags {
     filename: String,
     stop_signal: Arc<AtomicBool>,
+    pause_signal: Arc<AtomicBool>,
     width: u32,
     height: u32,
     fps: String,
+    system_audio_device: Option<String>,  // DirectShow loopback device
+    mic_device: Option<String>,           // DirectShow microphone device
+    target_window: Option<isize>,         // HWND when capturing a single window
+    crop_origin: (u32, u32),              // Top-left of the recorded area inside each frame
+    app: AppHandle,                       // For recording-tick events
+    codec: String,                        // Video encoder for the intermediate file
+    preset: String,                       // x264/x265 preset (NVENC uses its fastest preset)
+    crf: Option<u8>,                      // Constant quality, encoder default when unset
+    webcam: Option<WebcamOptions>,        // Picture-in-picture camera, composited by FFmpeg
 }
 
-// Capture Handler with constant framerate output
-struct CaptureHandler {
-    ffmpeg_process: std::process::Child,
+// Payload of the recording-tick event, emitted about once per second
+#[derive(serde::Serialize, Clone)]
+pub struct RecordingTick {
+    pub elapsed_seconds: u64,  // Recorded time, pauses excluded
+    pub frames: u64,
+    pub dropped_frames: u64,   // Captured frames skipped because the encoder fell behind
+}
+
+// Client area of a window: its position on screen, its offset inside the
+// captured frame (which spans the DWM frame bounds, title bar included) and
+// its size aligned down to even numbers for yuv420p
+struct ClientArea {
+    screen_x: i32,
+    screen_y: i32,
+    offset_x: u32,
+    offset_y: u32,
+    width: u32,
+    height: u32,
+}
+
+// Free space available to this user on the drive holding `dir`
+pub(crate) fn free_disk_space(dir: &std::path::Path) -> Result<u64, String> {
+    use windows::core::HSTRING;
+    use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let mut free_bytes: u64 = 0;
+    unsafe {
+        GetDiskFreeSpaceExW(&HSTRING::from(dir), Some(&mut free_bytes), None, None)
+            .map_err(|e| format!("Failed to get free disk space for {}: {}", dir.display(), e))?;
+    }
+    Ok(free_bytes)
+}
+
+// Directory a recording is written to (the working directory for bare names)
+pub(crate) fn output_dir(filename: &str) -> std::path::PathBuf {
+    match std::path::Path::new(filename).parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => std::path::PathBuf::from("."),
+    }
+}
+
+// Rough size of the intermediate recording per second. ultrafast x264 at
+// its default quality lands around 0.15 bits per pixel on screen content;
+// other codecs and presets produce less, so this errs on the safe side.
+pub(crate) fn estimated_bytes_per_second(width: u32, height: u32, fps: f64) -> u64 {
+    (width as f64 * height as f64 * fps * 0.15 / 8.0) as u64
+}
+
+// Refuse to start with room for less than a minute, warn below five
+const MIN_RECORDING_SECONDS: u64 = 60;
+const LOW_DISK_WARNING_SECONDS: u64 = 300;
+// How often the capture handler re-checks free space
+const DISK_CHECK_INTERVAL_SECONDS: u64 = 30;
+
+// Payload of the low-disk event, emitted once when free space runs low
+#[derive(serde::Serialize, Clone)]
+pub struct LowDiskWarning {
+    pub free_bytes: u64,
+    pub seconds_left: u64,  // Projected recording time that still fits
+}
+
+// Share of captured frames dropped from the encoder queue above which the
+// encoder is considered too slow for the recording settings. Below it the
+// odd drop is covered by repeating the previous frame and isn't visible
+const ENCODER_BOUND_DROP_RATIO: f64 = 0.02;
+
+// Frame accounting of a finished recording, also emitted as recording-stats.
+// Windows only delivers a frame when the screen changes, so duplicated
+// frames are normal for static content; dropped frames are what shows the
+// encoder falling behind
+#[derive(serde::Serialize, Clone)]
+pub struct RecordingStats {
+    pub target_fps: f64,
+    pub effective_fps: f64,     // Distinct captured frames per second of video
+    pub frames_written: u64,    // Frames in the video, duplicates included
+    pub frames_captured: u64,   // Distinct frames in the video
+    pub frames_duplicated: u64, // Repeats written to hold the constant framerate
+    pub frames_dropped: u64,    // Captured frames skipped because the encoder fell behind
+    pub encoder_bound: bool,    // Too many drops; a lower resolution or frame rate would help
+}
+
+fn window_client_area(hwnd: isize) -> Result<ClientArea, String> {
+    use windows::Win32::Foundation::{HWND, POINT, RECT};
+    use windows::Win32::Graphics::Dwm::{DwmGetWindowAttribute, DWMWA_EXTENDED_FRAME_BOUNDS};
+    use windows::Win32::Graphics::Gdi::ClientToScreen;
+    use windows::Win32::UI::WindowsAndMessaging::GetClientRect;
+
+    let hwnd = HWND(hwnd as *mut std::ffi::c_void);
+    let mut frame_bounds = RECT::default();
+    let mut client_rect = RECT::default();
+    let mut client_origin = POINT { x: 0, y: 0 };
+
+    unsafe {
+        DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_EXTENDED_FRAME_BOUNDS,
+            &mut frame_bounds as *mut RECT as *mut std::ffi::c_void,
+            std::mem::size_of::<RECT>() as u32,
+        )
+        .map_err(|e| format!("Failed to get window frame bounds: {}", e))?;
+        GetClientRect(hwnd, &mut client_rect)
+            .map_err(|e| format!("Failed to get window client rect: {}", e))?;
+        if !ClientToScreen(hwnd, &mut client_origin).as_bool() {
+            return Err("Failed to get window client position".to_string());
+        }
+    }
+
+    let width = ((client_rect.right - client_rect.left).max(0) as u32) & !1;
+    let height = ((client_rect.bottom - client_rect.top).max(0) as u32) & !1;
+    if width == 0 || height == 0 {
+        return Err("Window has no visible client area".to_string());
+    }
+
+    Ok(ClientArea {
+        screen_x: client_origin.x,
+        screen_y: client_origin.y,
+        offset_x: (client_origin.x - frame_bounds.left).max(0) as u32,
+        offset_y: (client_origin.y - frame_bounds.top).max(0) as u32,
+        width,
+        height,
+    })
+}
+
+// Audio or video input device for frontend
+#[derive(serde::Serialize, Clone)]
+pub struct CaptureDeviceInfo {
+    pub id: String,    // Value to pass back as mic_device / webcam device (DirectShow alternative name when available)
+    pub name: String,  // Friendly name
+}
+
+// List DirectShow capture devices of one kind ("audio" or "video") as reported by FFmpeg
+fn list_dshow_devices(kind: &str) -> Vec<CaptureDeviceInfo> {
+    let output = match Command::new(crate::ffmpeg::ffmpeg_path())
+        .args(&["-hide_banner", "-list_devices", "true", "-f", "dshow", "-i", "dummy"])
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to list {} devices: {}", kind, e);
+            return Vec::new();
+        }
+    };
+
+    // Device names are quoted, either tagged "(audio)"/"(video)" (newer FFmpeg)
+    // or listed under a "DirectShow audio/video devices" header (older FFmpeg).
+    // Each is followed by an "Alternative name" line holding a stable device id
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let header = format!("DirectShow {} devices", kind);
+    let tag = format!("({})", kind);
+    let mut devices: Vec<CaptureDeviceInfo> = Vec::new();
+    let mut in_section = false;
+    let mut last_matched = false;
+    for line in stderr.lines() {
+        if line.contains("DirectShow") && line.contains("devices") {
+            in_section = line.contains(&header);
+            continue;
+        }
+        let (Some(open), Some(close)) = (line.find('"'), line.rfind('"')) else {
+            continue;
+        };
+        if close <= open {
+            continue;
+        }
+        let quoted = line[open + 1..close].to_string();
+        if line.contains("Alternative name") {
+            if last_matched {
+                if let Some(device) = devices.last_mut() {
+                    device.id = quoted;
+                }
+            }
+            continue;
+        }
+        last_matched = in_section || line.trim_end().ends_with(&tag);
+        if last_matched {
+            devices.push(CaptureDeviceInfo { id: quoted.clone(), name: quoted });
+        }
+    }
+    devices
+}
+
+// Find a device that captures what's playing through the speakers
+fn find_loopback_audio_device() -> Option<String> {
+    const LOOPBACK_NAMES: [&str; 4] = ["stereo mix", "what u hear", "wave out mix", "virtual-audio-capturer"];
+    list_dshow_devices("audio").into_iter().find(|device| {
+        let lower = device.name.to_lowercase();
+        LOOPBACK_NAMES.iter().any(|loopback| lower.contains(loopback))
+    }).map(|device| device.id)
+}
+
+#[tauri::command]
+pub fn get_audio_devices() -> Vec<CaptureDeviceInfo> {
+    list_dshow_devices("audio")
+}
+
+#[tauri::command]
+pub fn get_video_devices() -> Vec<CaptureDeviceInfo> {
+    list_dshow_devices("video")
+}
+
+// Frames waiting for the encoder thread. When FFmpeg falls behind the queue
+// fills up and the oldest frame is dropped, so the capture callback never
+// blocks on a slow encoder; the constant-framerate catch-up then repeats the
+// next frame over the gap
+const FRAME_QUEUE_CAPACITY: usize = 8;
+
+// A captured frame as the capture API handed it over, rows possibly padded
+// and the recorded area possibly offset inside it
+struct QueuedFrame {
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+    captured_at: Instant,
+}
+
+#[derive(Default)]
+struct FrameQueueState {
+    frames: VecDeque<QueuedFrame>,
+    spare: Vec<Vec<u8>>,  // Converted or written buffers handed back for reuse
+    closed: bool,
+}
+
+#[derive(Default)]
+struct FrameQueue {
+    state: Mutex<FrameQueueState>,
+    ready: Condvar,
+    dropped: AtomicU64,
+}
+
+impl FrameQueue {
+    // A buffer to copy the next frame into, reusing a written one if possible
+    fn take_buffer(&self, size: usize) -> Vec<u8> {
+        let mut buffer = self.state.lock().unwrap().spare.pop().unwrap_or_default();
+        buffer.resize(size, 0);
+        buffer
+    }
+
+    fn push(&self, frame: QueuedFrame) {
+        let mut state = self.state.lock().unwrap();
+        if state.frames.len() >= FRAME_QUEUE_CAPACITY {
+            if let Some(oldest) = state.frames.pop_front() {
+                state.spare.push(oldest.data);
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        state.frames.push_back(frame);
+        self.ready.notify_one();
+    }
+
+    // Next frame, waiting for one; None once closed and drained
+    fn pop(&self) -> Option<QueuedFrame> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(frame) = state.frames.pop_front() {
+                return Some(frame);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.ready.wait(state).unwrap();
+        }
+    }
+
+    fn recycle(&self, buffer: Vec<u8>) {
+        self.state.lock().unwrap().spare.push(buffer);
+    }
+
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.ready.notify_all();
+    }
+}
+
+// Encoder side of a recording: runs on its own thread, writes queued frames
+// to FFmpeg at a constant framerate and reports progress to the frontend
+struct FrameWriter {
+    stdin: std::process::ChildStdin,
+    queue: Arc<FrameQueue>,
     stop_signal: Arc<AtomicBool>,
     recording_start: Option<Instant>,
     frames_written: u64,
+    frames_received: u64,
+    frames_captured: u64,
+    frames_duplicated: u64,
     target_fps: f64,
-    last_frame: Vec<u8>,
     frame_width: u32,
     frame_height: u32,
+    crop_origin: (u32, u32),
+    last_frame: Vec<u8>,  // Tightly packed frame_width x frame_height BGRA
+    app: AppHandle,
+    last_tick_seconds: Option<u64>,
+    reported_dropped: u64,
+    output_dir: std::path::PathBuf,
+    bytes_per_second: u64,
+    low_disk_warned: bool,
+}
+
+impl FrameWriter {
+    fn run(mut self) {
+        while let Some(frame) = self.queue.pop() {
+            if let Err(e) = self.write(frame) {
+                // FFmpeg is gone; end the capture instead of queueing forever
+                eprintln!("Failed to write frame to FFmpeg: {}", e);
+                self.stop_signal.store(false, Ordering::Relaxed);
+                break;
+            }
+        }
+
+        let stats = self.stats();
+        if stats.encoder_bound {
+            eprintln!(
+                "Encoder couldn't keep up: {} of {} captured frames dropped ({:.1} fps effective, {} fps target)",
+                stats.frames_dropped, self.frames_received + stats.frames_dropped, stats.effective_fps, stats.target_fps
+            );
+        }
+        *LAST_RECORDING_STATS.lock().unwrap() = Some(stats.clone());
+        let _ = self.app.emit("recording-stats", stats);
+        // Dropping stdin here tells FFmpeg the video has ended
+    }
+
+    fn stats(&self) -> RecordingStats {
+        let frames_dropped = self.queue.dropped.load(Ordering::Relaxed);
+        let seconds = self.frames_written as f64 / self.target_fps;
+        let offered = self.frames_received + frames_dropped;
+        RecordingStats {
+            target_fps: self.target_fps,
+            effective_fps: if seconds > 0.0 { self.frames_captured as f64 / seconds } else { 0.0 },
+            frames_written: self.frames_written,
+            frames_captured: self.frames_captured,
+            frames_duplicated: self.frames_duplicated,
+            frames_dropped,
+            encoder_bound: offered > 0 && frames_dropped as f64 / offered as f64 > ENCODER_BOUND_DROP_RATIO,
+        }
+    }
+
+    // Turn a raw capture buffer into the next frame for FFmpeg: drop the row
+    // padding and cut out the recorded area. Done here rather than in the
+    // capture callback, which only copies the buffer, so per-row work never
+    // holds up frame delivery
+    fn convert(&mut self, frame: QueuedFrame) {
+        if frame.width == 0 || frame.height == 0 {
+            // Nothing to cut from; keep repeating the previous frame
+            self.queue.recycle(frame.data);
+            return;
+        }
+        let row_pitch = frame.data.len() / frame.height as usize;
+        let tight_pitch = (frame.width * 4) as usize;
+        let frame_size = (self.frame_width * self.frame_height * 4) as usize;
+
+        let (crop_x, crop_y) = self.crop_origin;
+        if crop_x == 0 && crop_y == 0 && row_pitch == tight_pitch
+            && frame.width == self.frame_width && frame.height == self.frame_height
+            && frame.data.len() == frame_size
+        {
+            // Already in FFmpeg's layout, use the buffer as is
+            let previous = std::mem::replace(&mut self.last_frame, frame.data);
+            self.queue.recycle(previous);
+            return;
+        }
+
+        // Part of the recorded area the frame actually covers. A captured
+        // window can shrink, or a display change its resolution, mid-recording,
+        // leaving the crop partly outside the frame; that part is filled black
+        // instead of reading past the end of each row
+        let visible_width = frame.width.saturating_sub(crop_x).min(self.frame_width);
+        let visible_height = frame.height.saturating_sub(crop_y).min(self.frame_height);
+        let copy_len = (visible_width as usize * 4).min(row_pitch.saturating_sub(crop_x as usize * 4));
+
+        self.last_frame.resize(frame_size, 0);
+        if visible_width < self.frame_width || visible_height < self.frame_height {
+            self.last_frame.fill(0);
+        }
+        let dst_pitch = (self.frame_width * 4) as usize;
+        for i in 0..visible_height as usize {
+            let src_start = (i + crop_y as usize) * row_pitch + crop_x as usize * 4;
+            let dst_start = i * dst_pitch;
+            if src_start + copy_len <= frame.data.len() {
+                self.last_frame[dst_start..dst_start + copy_len]
+                    .copy_from_slice(&frame.data[src_start..src_start + copy_len]);
+            }
+        }
+        self.queue.recycle(frame.data);
+    }
+
+    fn write(&mut self, frame: QueuedFrame) -> std::io::Result<()> {
+        let captured_at = frame.captured_at;
+        self.convert(frame);
+
+        if self.recording_start.is_none() {
+            self.recording_start = Some(captured_at);
+        }
+        {
+            // Cursor and click timestamps share the video's clock. resume_recording
+            // shifts it forward by the paused time, so read it back every frame
+            // and the catch-up logic below doesn't fill the pause with duplicates
+            let mut start_time = RECORDING_START_TIME.lock().unwrap();
+            if start_time.is_none() {
+                *start_time = self.recording_start;
+            }
+            self.recording_start = *start_time;
+        }
+
+        let elapsed = captured_at.saturating_duration_since(self.recording_start.unwrap());
+        let expected_frames = (elapsed.as_secs_f64() * self.target_fps).ceil() as u64;
+        let written_before = self.frames_written;
+        while self.frames_written < expected_frames {
+            self.stdin.write_all(&self.last_frame)?;
+            self.frames_written += 1;
+        }
+        self.frames_received += 1;
+        // A frame arriving faster than the framerate isn't written at all;
+        // one arriving late is repeated over the gap
+        let writes = self.frames_written - written_before;
+        if writes > 0 {
+            self.frames_captured += 1;
+            self.frames_duplicated += writes - 1;
+        }
+
+        // Tick the frontend timer whenever another whole second has been written
+        let elapsed_seconds = (self.frames_written as f64 / self.target_fps) as u64;
+        if self.last_tick_seconds != Some(elapsed_seconds) {
+            self.last_tick_seconds = Some(elapsed_seconds);
+            let dropped_frames = self.queue.dropped.load(Ordering::Relaxed);
+            if dropped_frames > self.reported_dropped {
+                eprintln!("Encoder can't keep up: {} frames dropped so far", dropped_frames);
+                self.reported_dropped = dropped_frames;
+            }
+            let _ = self.app.emit("recording-tick", RecordingTick {
+                elapsed_seconds,
+                frames: self.frames_written,
+                dropped_frames,
+            });
+            if elapsed_seconds % DISK_CHECK_INTERVAL_SECONDS == 0 {
+                self.check_disk_space();
+            }
+        }
+        Ok(())
+    }
+
+    // Warn the frontend once when the drive can only hold a few more minutes
+    fn check_disk_space(&mut self) {
+        if self.low_disk_warned || self.bytes_per_second == 0 {
+            return;
+        }
+        let free_bytes = match free_disk_space(&self.output_dir) {
+            Ok(free_bytes) => free_bytes,
+            Err(e) => {
+                eprintln!("{}", e);
+                return;
+            }
+        };
+        let seconds_left = free_bytes / self.bytes_per_second;
+        if seconds_left < LOW_DISK_WARNING_SECONDS {
+            eprintln!("Low disk space: {} MB free, about {}s of recording left", free_bytes / 1_000_000, seconds_left);
+            self.low_disk_warned = true;
+            let _ = self.app.emit("low-disk", LowDiskWarning { free_bytes, seconds_left });
+        }
+    }
+}
+
+// Capture Handler with constant framerate output. The capture callback only
+// copies raw buffers into the queue; a FrameWriter thread converts and
+// encodes them
+struct CaptureHandler {
+    ffmpeg_process: std::process::Child,
+    stop_signal: Arc<AtomicBool>,
+    pause_signal: Arc<AtomicBool>,
+    target_window: Option<isize>,
+    queue: Arc<FrameQueue>,
+    writer: Option<thread::JoinHandle<()>>,
 }
 
 impl GraphicsCaptureApiHandler for CaptureHandler {
@@ -100,33 +610,110 @@ impl GraphicsCaptureApiHandler for CaptureHandler {
         let width = if flags.width % 2 != 0 { flags.width - 1 } else { flags.width };
         let height = if flags.height % 2 != 0 { flags.height - 1 } else { flags.height };
 
-        let child = Command::new("ffmpeg")
-            .args(&[
-                "-f", "rawvideo",
-                "-pixel_format", "bgra",
-                "-video_size", &format!("{}x{}", width, height),
-                "-framerate", &flags.fps,
-                "-i", "-",
-                "-c:v", "libx264",
-                "-pix_fmt", "yuv420p",
-                "-preset", "ultrafast",
-                "-r", &flags.fps,
-                "-y",
-                &flags.filename
-            ])
+        let mut args: Vec<String> = vec![
+            "-f".to_string(), "rawvideo".to_string(),
+            "-pixel_format".to_string(), "bgra".to_string(),
+            "-video_size".to_string(), format!("{}x{}", width, height),
+            "-framerate".to_string(), flags.fps.clone(),
+            "-i".to_string(), "-".to_string(),
+        ];
+        // Each audio source is another DirectShow input after the piped video
+        let audio_devices: Vec<&String> = [&flags.system_audio_device, &flags.mic_device]
+            .into_iter()
+            .flatten()
+            .collect();
+        for device in &audio_devices {
+            println!("Capturing audio from: {}", device);
+            args.extend([
+                "-f".to_string(), "dshow".to_string(),
+                "-thread_queue_size".to_string(), "1024".to_string(),
+                "-i".to_string(), format!("audio={}", device),
+            ]);
+        }
+        // The webcam comes last and is composited onto the screen by FFmpeg,
+        // so recordings without it keep the plain piped-video path
+        if let Some(webcam) = &flags.webcam {
+            println!("Capturing webcam: {}", webcam.device);
+            args.extend([
+                "-f".to_string(), "dshow".to_string(),
+                "-thread_queue_size".to_string(), "1024".to_string(),
+                "-i".to_string(), format!("video={}", webcam.device),
+            ]);
+        }
+        
+        let mut filters: Vec<String> = Vec::new();
+        if let Some(webcam) = &flags.webcam {
+            filters.push(webcam_overlay_filter(webcam, 1 + audio_devices.len(), width));
+        }
+        if audio_devices.len() == 2 {
+            // System audio and microphone mixed into a single track
+            filters.push("[1:a][2:a]amix=inputs=2:duration=longest[aout]".to_string());
+        }
+        if !filters.is_empty() {
+            args.extend(["-filter_complex".to_string(), filters.join(";")]);
+        }
+        if !audio_devices.is_empty() || flags.webcam.is_some() {
+            let video_out = if flags.webcam.is_some() { "[vout]" } else { "0:v" };
+            args.extend(["-map".to_string(), video_out.to_string()]);
+            match audio_devices.len() {
+                2 => args.extend(["-map".to_string(), "[aout]".to_string()]),
+                1 => args.extend(["-map".to_string(), "1:a".to_string()]),
+                _ => {}
+            }
+        }
+        if !audio_devices.is_empty() {
+            args.extend([
+                "-c:a".to_string(), "aac".to_string(),
+                "-b:a".to_string(), "192k".to_string(),
+                // Audio never reaches EOF on its own, so end with the video
+                "-shortest".to_string(),
+            ]);
+        }
+        args.extend(recording_output_args(&flags));
+
+        let mut child = Command::new(crate::ffmpeg::ffmpeg_path())
+            .args(&args)
             .stdin(Stdio::piped())
             .stderr(Stdio::inherit())
             .spawn()?;
 
-        Ok(Self {
-            ffmpeg_process: child,
-            stop_signal: flags.stop_signal,
-            recording_start: None,
+        // Audio and webcam start flowing as soon as FFmpeg opens the device, so
+        // start the video clock now too; the constant-framerate logic then pads
+        // the gap before the first captured frame and all streams share one timeline
+        let live_inputs = !audio_devices.is_empty() || flags.webcam.is_some();
+        let recording_start = if live_inputs { Some(Instant::now()) } else { None };
+
+        let queue = Arc::new(FrameQueue::default());
+        let writer = FrameWriter {
+            stdin: child.stdin.take().ok_or("FFmpeg stdin is not available")?,
+            queue: queue.clone(),
+            stop_signal: flags.stop_signal.clone(),
+            recording_start,
             frames_written: 0,
+            frames_received: 0,
+            frames_captured: 0,
+            frames_duplicated: 0,
             target_fps: fps_value,
-            last_frame: Vec::new(),
             frame_width: width,
             frame_height: height,
+            crop_origin: flags.crop_origin,
+            last_frame: Vec::new(),
+            app: flags.app,
+            last_tick_seconds: None,
+            reported_dropped: 0,
+            output_dir: output_dir(&flags.filename),
+            bytes_per_second: estimated_bytes_per_second(width, height, fps_value),
+            low_disk_warned: false,
+        };
+        let writer = thread::spawn(move || writer.run());
+
+        Ok(Self {
+            ffmpeg_process: child,
+            stop_signal: flags.stop_signal,
+            pause_signal: flags.pause_signal,
+            target_window: flags.target_window,
+            queue,
+            writer: Some(writer),
         })
     }
 
@@ -136,55 +723,58 @@ impl GraphicsCaptureApiHandler for CaptureHandler {
             return Ok(());
         }
 
-        let width = frame.width();
-        let height = frame.height();
-        let mut buffer_obj = frame.buffer()?;
-        let src_data = buffer_obj.as_raw_buffer();
-
-        let row_pitch = src_data.len() / height as usize;
-        let tight_pitch = (width * 4) as usize;
-        let frame_size = (self.frame_width * self.frame_height * 4) as usize;
-
-        if self.last_frame.len() != frame_size {
-            self.last_frame = vec![0u8; frame_size];
-        }
-        
-        if row_pitch == tight_pitch && width == self.frame_width && height == self.frame_height {
-            self.last_frame.copy_from_slice(&src_data[..frame_size]);
-        } else {
-            for i in 0..self.frame_height as usize {
-                let src_start = i * row_pitch;
-                let dst_start = i * (self.frame_width * 4) as usize;
-                let copy_len = (self.frame_width * 4) as usize;
-                if src_start + copy_len <= src_data.len() {
-                    self.last_frame[dst_start..dst_start + copy_len]
-                        .copy_from_slice(&src_data[src_start..src_start + copy_len]);
-                }
+        // The captured window can be closed mid-recording; end the capture cleanly
+        if let Some(hwnd) = self.target_window {
+            if Window::from_raw_hwnd(hwnd as *mut std::ffi::c_void).rect().is_err() {
+                println!("Captured window was closed, stopping recording");
+                self.stop_signal.store(false, Ordering::Relaxed);
+                capture_control.stop();
+                return Ok(());
             }
         }
 
-        let now = Instant::now();
-        if self.recording_start.is_none() {
-            self.recording_start = Some(now);
+        // While paused nothing is written; FFmpeg stays alive waiting for frames
+        if self.pause_signal.load(Ordering::Relaxed) {
+            return Ok(());
         }
 
-        let elapsed = now.duration_since(self.recording_start.unwrap());
-        let expected_frames = (elapsed.as_secs_f64() * self.target_fps).ceil() as u64;
-
-        if let Some(stdin) = self.ffmpeg_process.stdin.as_mut() {
-            while self.frames_written < expected_frames {
-                stdin.write_all(&self.last_frame)?;
-                self.frames_written += 1;
+        let width = frame.width();
+        let height = frame.height();
+        // Mapping the frame can fail mid-resize (a resolution or DPI change).
+        // Skip that frame instead of returning the error, which would end the
+        // capture; the writer repeats the previous frame over the gap
+        let mut buffer_obj = match frame.buffer() {
+            Ok(buffer) => buffer,
+            Err(e) => {
+                eprintln!("Skipping a {}x{} frame that couldn't be read: {:?}", width, height, e);
+                return Ok(());
             }
+        };
+        let src_data = buffer_obj.as_raw_buffer();
+        // A buffer shorter than its reported size would leave FrameWriter
+        // converting rows that aren't there, so drop it like an unreadable one
+        if src_data.len() < width as usize * height as usize * 4 {
+            eprintln!("Skipping a {}x{} frame with only {} bytes", width, height, src_data.len());
+            return Ok(());
         }
 
+        let mut data = self.queue.take_buffer(src_data.len());
+        data.copy_from_slice(src_data);
+
+        self.queue.push(QueuedFrame { data, width, height, captured_at: Instant::now() });
         Ok(())
     }
 
     fn on_closed(&mut self) -> Result<(), Self::Error> {
         println!("Capture closed. Cleaning up ffmpeg.");
-        if let Some(stdin) = self.ffmpeg_process.stdin.take() {
-            drop(stdin);
+        // Let the writer drain what's queued; it closes FFmpeg's stdin when done
+        self.queue.close();
+        if let Some(writer) = self.writer.take() {
+            writer.join().ok();
+        }
+        let dropped = self.queue.dropped.load(Ordering::Relaxed);
+        if dropped > 0 {
+            println!("Dropped {} frames because the encoder fell behind", dropped);
         }
         self.ffmpeg_process.wait()?;
         println!("FFmpeg finished.");
@@ -233,49 +823,81 @@ pub fn get_open_windows() -> Vec<WindowInfo> {
     result
 }
 
-// Spawn a mouse listener thread that captures clicks during recording
-fn spawn_mouse_listener(stop_signal: Arc<AtomicBool>) {
-    use rdev::{listen, Event, EventType, Button};
-    
-    thread::spawn(move || {
-        let callback = move |event: Event| {
-            // Only process while recording
-            if !stop_signal.load(Ordering::Relaxed) {
-                return;
-            }
-            
-            if let EventType::ButtonPress(Button::Left) = event.event_type {
-                let now = Instant::now();
-                
-                // Get screen size for normalization
-                let (screen_w, screen_h) = *SCREEN_SIZE.lock().unwrap();
-                
-                // Get mouse position (rdev provides screen coordinates)
-                let (x, y) = match (event.name, event.time) {
-                    _ => {
-                        // rdev doesn't give position in ButtonPress, we need to track it
-                        // Use MouseMove events to track position
-                        (0.0, 0.0)
-                    }
-                };
-                
-                // We'll track position from MouseMove events
-                // For now, we'll capture the click without position
-                // and update this logic
-            }
-        };
-        
-        // This blocks, so it runs in its own thread
-        if let Err(e) = listen(callback) {
-            eprintln!("Mouse listener error: {:?}", e);
+// Monitor Info for frontend
+#[derive(serde::Serialize, Clone)]
+pub struct MonitorInfo {
+    pub index: usize,   // One-based, as expected by Monitor::from_index
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub x: i32,         // Desktop position of the top-left corner
+    pub y: i32,
+    pub is_primary: bool,
+}
+
+// Desktop origin of a monitor. windows-capture only exposes the size, so match
+// its device name (e.g. \\.\DISPLAY2) against the monitors Tauri reports
+fn monitor_origin(app: &AppHandle, monitor: &Monitor) -> (i32, i32) {
+    let Ok(device_name) = monitor.device_name() else {
+        return (0, 0);
+    };
+    app.available_monitors()
+        .ok()
+        .and_then(|monitors| {
+            monitors.into_iter().find(|m| m.name().map_or(false, |name| *name == device_name))
+        })
+        .map(|m| (m.position().x, m.position().y))
+        .unwrap_or((0, 0))
+}
+
+#[tauri::command]
+pub fn get_monitors(app: AppHandle) -> Vec<MonitorInfo> {
+    let mut result = Vec::new();
+    let primary = Monitor::primary().ok();
+
+    if let Ok(monitors) = Monitor::enumerate() {
+        for (i, monitor) in monitors.into_iter().enumerate() {
+            let name = monitor.name()
+                .or_else(|_| monitor.device_string())
+                .unwrap_or_else(|_| format!("Display {}", i + 1));
+            let (x, y) = monitor_origin(&app, &monitor);
+            result.push(MonitorInfo {
+                index: i + 1,
+                name,
+                width: monitor.width().unwrap_or(0),
+                height: monitor.height().unwrap_or(0),
+                x,
+                y,
+                is_primary: primary == Some(monitor),
+            });
         }
-    });
+    }
+
+    result
 }
 
+// Set once the mouse listener thread is running. rdev::listen never returns
+// while it works, so one listener serves every recording
+static MOUSE_LISTENER_STARTED: AtomicBool = AtomicBool::new(false);
+
 // Better mouse listener that tracks position and samples cursor during recording
-fn spawn_mouse_listener_v2(stop_signal: Arc<AtomicBool>) {
+// Started on the first recording and kept for the life of the process. The
+// arguments are RecorderState's shared flags and settings, so each event sees
+// the current recording: `multi_click_ms` is the longest gap between clicks
+// that still counts them as one double or triple click, and `zoom_factor` is
+// read at each triple-click, so set_zoom_factor applies to the zooms that follow it
+fn spawn_mouse_listener_v2(
+    stop_signal: Arc<AtomicBool>,
+    pause_signal: Arc<AtomicBool>,
+    multi_click_ms: Arc<AtomicU64>,
+    zoom_factor: Arc<Mutex<f64>>,
+) {
     use rdev::{listen, Event, EventType, Button};
     
+    if MOUSE_LISTENER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    
     thread::spawn(move || {
         let mut last_mouse_x: f64 = 0.0;
         let mut last_mouse_y: f64 = 0.0;
@@ -286,55 +908,47 @@ fn spawn_mouse_listener_v2(stop_signal: Arc<AtomicBool>) {
                 last_mouse_x = x;
                 last_mouse_y = y;
                 
-                // Sample cursor position every 50ms during recording (for cursor-following zoom)
-                if stop_signal.load(Ordering::Relaxed) {
-                    let now = Instant::now();
-                    let should_sample = {
-                        let mut last_sample = LAST_CURSOR_SAMPLE.lock().unwrap();
-                        match *last_sample {
-                            Some(last) if now.duration_since(last).as_millis() < 50 => false,
-                            _ => {
-                                *last_sample = Some(now);
-                                true
-                            }
-                        }
+                // Record every move during recording (for cursor-following zoom)
+                if stop_signal.load(Ordering::Relaxed) && !pause_signal.load(Ordering::Relaxed) {
+                    // Timestamps are relative to the first captured frame, so skip
+                    // moves that happen before the video has actually started
+                    let start = match *RECORDING_START_TIME.lock().unwrap() {
+                        Some(start) => start,
+                        None => return,
                     };
+                    let timestamp_ms = Instant::now().duration_since(start).as_millis() as u64;
                     
-                    if should_sample {
-                        // Transform screen coordinates to capture-relative coordinates
-                        let (cap_x, cap_y, cap_w, cap_h) = *CAPTURE_BOUNDS.lock().unwrap();
-                        let rel_x = last_mouse_x - cap_x as f64;
-                        let rel_y = last_mouse_y - cap_y as f64;
-                        let norm_x = rel_x / cap_w as f64;
-                        let norm_y = rel_y / cap_h as f64;
-                        
-                        // Only record if cursor is within capture bounds
-                        if norm_x >= 0.0 && norm_x <= 1.0 && norm_y >= 0.0 && norm_y <= 1.0 {
-                            let timestamp_ms = {
-                                if let Some(start) = *RECORDING_START_TIME.lock().unwrap() {
-                                    now.duration_since(start).as_millis() as u64
-                                } else {
-                                    0
-                                }
-                            };
-                            
-                            CURSOR_POSITIONS.lock().unwrap().push(CursorPosition {
-                                timestamp_ms,
-                                x: norm_x,
-                                y: norm_y,
-                            });
-                        }
+                    // Transform screen coordinates to capture-relative coordinates
+                    let (cap_x, cap_y, cap_w, cap_h) = *CAPTURE_BOUNDS.lock().unwrap();
+                    let rel_x = last_mouse_x - cap_x as f64;
+                    let rel_y = last_mouse_y - cap_y as f64;
+                    let norm_x = rel_x / cap_w as f64;
+                    let norm_y = rel_y / cap_h as f64;
+                    
+                    // Only record if cursor is within capture bounds
+                    if norm_x >= 0.0 && norm_x <= 1.0 && norm_y >= 0.0 && norm_y <= 1.0 {
+                        CURSOR_POSITIONS.lock().unwrap().push(CursorPosition {
+                            timestamp_ms,
+                            x: norm_x,
+                            y: norm_y,
+                        });
                     }
                 }
                 return;
             }
             
-            // Only process clicks while recording
-            if !stop_signal.load(Ordering::Relaxed) {
+            // Only process clicks while recording (and not paused)
+            if !stop_signal.load(Ordering::Relaxed) || pause_signal.load(Ordering::Relaxed) {
                 return;
             }
             
-            if let EventType::ButtonPress(Button::Left) = event.event_type {
+            if let EventType::ButtonPress(button) = event.event_type {
+                let button_name = match button {
+                    Button::Left => "left",
+                    Button::Right => "right",
+                    Button::Middle => "middle",
+                    Button::Unknown(_) => return,
+                };
                 let now = Instant::now();
                 
                 // Transform screen coordinates to capture-relative coordinates
@@ -349,66 +963,65 @@ fn spawn_mouse_listener_v2(stop_signal: Arc<AtomicBool>) {
                     return;
                 }
                 
-                // Get timestamp since recording start
-                let timestamp_ms = {
-                    if let Some(start) = *RECORDING_START_TIME.lock().unwrap() {
-                        now.duration_since(start).as_millis() as u64
-                    } else {
-                        0
-                    }
+                // Get timestamp since the first captured frame (same clock as cursor positions)
+                let timestamp_ms = match *RECORDING_START_TIME.lock().unwrap() {
+                    Some(start) => now.duration_since(start).as_millis() as u64,
+                    None => return,
                 };
                 
-                // Check for triple-click (3 clicks within 800ms and close position)
-                // Triple-click is the new zoom trigger (instead of double-click)
-                let is_triple_click = {
-                    let mut last_two = LAST_TWO_CLICKS.lock().unwrap();
-                    
-                    // Check if we have two previous clicks that form a triple with this one
-                    let is_triple = if last_two.len() >= 2 {
-                        let (first_time, first_x, first_y) = last_two[last_two.len() - 2];
-                        let (second_time, second_x, second_y) = last_two[last_two.len() - 1];
+                // Multi-click detection (and the zoom trigger) only applies to the left button
+                let multi_click_ms = multi_click_ms.load(Ordering::Relaxed);
+                let (is_double_click, is_triple_click) = if matches!(button, Button::Left) {
+                    // Check for triple-click (3 clicks, each within the threshold of the
+                    // previous one, close in position)
+                    // Triple-click is the new zoom trigger (instead of double-click)
+                    let is_triple_click = {
+                        let mut last_two = LAST_TWO_CLICKS.lock().unwrap();
+                        
+                        // Check if we have two previous clicks that form a triple with this one
+                        let is_triple = if last_two.len() >= 2 {
+                            let (first_time, first_x, first_y) = last_two[last_two.len() - 2];
+                            let (second_time, second_x, second_y) = last_two[last_two.len() - 1];
+                            
+                            // All 3 clicks must be within two thresholds total and close in position
+                            let time_from_first = now.duration_since(first_time).as_millis() as u64;
+                            let time_from_second = now.duration_since(second_time).as_millis() as u64;
+                            let dist_from_first = ((norm_x - first_x).powi(2) + (norm_y - first_y).powi(2)).sqrt();
+                            let dist_from_second = ((norm_x - second_x).powi(2) + (norm_y - second_y).powi(2)).sqrt();
+                            
+                            time_from_first < multi_click_ms * 2 && time_from_second < multi_click_ms && dist_from_first < 0.05 && dist_from_second < 0.05
+                        } else {
+                            false
+                        };
                         
-                        // All 3 clicks must be within 800ms total and close in position
-                        let time_from_first = now.duration_since(first_time).as_millis();
-                        let time_from_second = now.duration_since(second_time).as_millis();
-                        let dist_from_first = ((norm_x - first_x).powi(2) + (norm_y - first_y).powi(2)).sqrt();
-                        let dist_from_second = ((norm_x - second_x).powi(2) + (norm_y - second_y).powi(2)).sqrt();
+                        // Add current click to history (keep only last 2)
+                        last_two.push((now, norm_x, norm_y));
+                        if last_two.len() > 2 {
+                            last_two.remove(0);
+                        }
                         
-                        time_from_first < 800 && time_from_second < 400 && dist_from_first < 0.05 && dist_from_second < 0.05
-                    } else {
-                        false
+                        is_triple
                     };
                     
-                    // Add current click to history (keep only last 2)
-                    last_two.push((now, norm_x, norm_y));
-                    if last_two.len() > 2 {
-                        last_two.remove(0);
-                    }
-                    
-                    is_triple
-                };
-                
-                // Also track double-click for backwards compatibility
-                let is_double_click = {
-                    let mut last_click = LAST_CLICK.lock().unwrap();
-                    let is_double = if let Some((last_time, last_x, last_y)) = *last_click {
-                        let time_diff = now.duration_since(last_time).as_millis();
-                        let dist = ((norm_x - last_x).powi(2) + (norm_y - last_y).powi(2)).sqrt();
-                        time_diff < 500 && dist < 0.05
-                    } else {
-                        false
+                    // Also track double-click for backwards compatibility
+                    let is_double_click = {
+                        let mut last_click = LAST_CLICK.lock().unwrap();
+                        let is_double = if let Some((last_time, last_x, last_y)) = *last_click {
+                            let time_diff = now.duration_since(last_time).as_millis() as u64;
+                            let dist = ((norm_x - last_x).powi(2) + (norm_y - last_y).powi(2)).sqrt();
+                            time_diff < multi_click_ms && dist < 0.05
+                        } else {
+                            false
+                        };
+                        *last_click = Some((now, norm_x, norm_y));
+                        is_double
                     };
-                    *last_click = Some((now, norm_x, norm_y));
-                    is_double
-                };
-                
-                // Only store triple-clicks (that's what triggers zoom now)
-                // But first, check if we're in a zoom cooldown period
-                // Principle: A zoom lasts ~3 seconds, so ignore clicks during that time
-                if is_triple_click {
-                    const ZOOM_COOLDOWN_MS: u128 = 3000; // Match typical zoom duration
                     
-                    let should_trigger_zoom = {
+                    // A triple-click only counts as a zoom trigger outside the cooldown period
+                    // Principle: A zoom lasts ~3 seconds, so ignore clicks during that time
+                    let triggers_zoom = is_triple_click && {
+                        const ZOOM_COOLDOWN_MS: u128 = 3000; // Match typical zoom duration
+                        
                         let mut last_zoom = LAST_ZOOM_TRIGGER.lock().unwrap();
                         let in_cooldown = if let Some(last_trigger_time) = *last_zoom {
                             now.duration_since(last_trigger_time).as_millis() < ZOOM_COOLDOWN_MS
@@ -429,18 +1042,25 @@ fn spawn_mouse_listener_v2(stop_signal: Arc<AtomicBool>) {
                         }
                     };
                     
-                    if should_trigger_zoom {
-                        let click_event = ClickEvent {
-                            timestamp_ms,
-                            x: norm_x,
-                            y: norm_y,
-                            is_double_click,
-                            is_triple_click: true,
-                        };
-                        println!("Triple-click captured at ({:.3}, {:.3}) in video coords @ {}ms", norm_x, norm_y, timestamp_ms);
-                        CLICK_EVENTS.lock().unwrap().push(click_event);
-                    }
+                    (is_double_click, triggers_zoom)
+                } else {
+                    (false, false)
+                };
+                
+                if is_triple_click {
+                    println!("Triple-click captured at ({:.3}, {:.3}) in video coords @ {}ms", norm_x, norm_y, timestamp_ms);
                 }
+                
+                // Store every click; the editor filters on is_triple_click for zooms
+                CLICK_EVENTS.lock().unwrap().push(ClickEvent {
+                    timestamp_ms,
+                    x: norm_x,
+                    y: norm_y,
+                    button: button_name.to_string(),
+                    is_double_click,
+                    is_triple_click,
+                    zoom_scale: if is_triple_click { Some(*zoom_factor.lock().unwrap()) } else { None },
+                });
             }
         };
         
@@ -448,6 +1068,8 @@ fn spawn_mouse_listener_v2(stop_signal: Arc<AtomicBool>) {
         if let Err(e) = listen(callback) {
             eprintln!("Mouse listener error: {:?}", e);
         }
+        // Let the next recording try to start a listener again
+        MOUSE_LISTENER_STARTED.store(false, Ordering::SeqCst);
     });
 }
 
@@ -455,14 +1077,298 @@ fn spawn_mouse_listener_v2(stop_signal: Arc<AtomicBool>) {
 pub struct RecordTarget {
     #[serde(rename = "type")]
     pub target_type: String,
-    pub id: Option<i64>,  // HWND as i64 for JSON compatibility
+    pub id: Option<i64>,  // HWND for "window"
+    pub monitor_index: Option<usize>,  // One-based, for "monitor" and "region" (primary if unset)
+    // Rectangle for "region", relative to the top-left of the monitor
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+// Clamp a region to the monitor and align its size to even numbers for yuv420p.
+// Returns (x, y, width, height) relative to the monitor's top-left.
+fn clamp_region(target: &RecordTarget, monitor_w: u32, monitor_h: u32) -> Result<(u32, u32, u32, u32), String> {
+    let (x, y, width, height) = match (target.x, target.y, target.width, target.height) {
+        (Some(x), Some(y), Some(w), Some(h)) => (x, y, w, h),
+        _ => return Err("Region target requires x, y, width and height".to_string()),
+    };
+    
+    let left = x.clamp(0, monitor_w as i32) as u32;
+    let top = y.clamp(0, monitor_h as i32) as u32;
+    let right = (x as i64 + width as i64).clamp(0, monitor_w as i64) as u32;
+    let bottom = (y as i64 + height as i64).clamp(0, monitor_h as i64) as u32;
+    
+    let width = right.saturating_sub(left) & !1;
+    let height = bottom.saturating_sub(top) & !1;
+    if width == 0 || height == 0 {
+        return Err("Selected region is outside the monitor".to_string());
+    }
+    
+    Ok((left, top, width, height))
+}
+
+// Encoder settings for the intermediate recording. Defaults match the old
+// hardcoded behavior: libx264, ultrafast preset, no explicit rate control.
+#[derive(serde::Deserialize, Debug, Default)]
+pub struct RecordingOptions {
+    pub codec: Option<String>,   // "libx264" (default), "libx265" or "h264_nvenc"
+    pub crf: Option<u8>,         // 0-51, lower = better quality and bigger file
+    pub preset: Option<String>,  // x264/x265 preset, "ultrafast" by default
+}
+
+const RECORDING_CODECS: [&str; 3] = ["libx264", "libx265", "h264_nvenc"];
+
+const X264_PRESETS: [&str; 9] = [
+    "ultrafast", "superfast", "veryfast", "faster", "fast",
+    "medium", "slow", "slower", "veryslow",
+];
+
+impl RecordingOptions {
+    // Check user-supplied values before starting, so a typo is reported
+    // instead of FFmpeg failing to start on the capture thread
+    fn validate(&self) -> Result<(), String> {
+        if let Some(codec) = &self.codec {
+            if !RECORDING_CODECS.contains(&codec.as_str()) {
+                return Err(format!("Invalid codec \"{}\": expected one of {}", codec, RECORDING_CODECS.join(", ")));
+            }
+        }
+        if let Some(crf) = self.crf {
+            if crf > 51 {
+                return Err(format!("Invalid CRF {}: must be between 0 and 51", crf));
+            }
+        }
+        if let Some(preset) = &self.preset {
+            if !X264_PRESETS.contains(&preset.as_str()) {
+                return Err(format!("Invalid preset \"{}\": expected one of {}", preset, X264_PRESETS.join(", ")));
+            }
+        }
+        Ok(())
+    }
+    
+    // Requested codec if this FFmpeg build has it, libx264 otherwise
+    fn resolve_codec(&self) -> String {
+        match self.codec.as_deref() {
+            Some(codec) if codec != "libx264" => {
+                if crate::ffmpeg::encoder_available(codec) {
+                    codec.to_string()
+                } else {
+                    eprintln!("Encoder {} is not available in this FFmpeg build, recording with libx264", codec);
+                    "libx264".to_string()
+                }
+            }
+            _ => "libx264".to_string(),
+        }
+    }
+}
+
+// Picture-in-picture webcam for a recording
+#[derive(serde::Deserialize, Debug, Clone)]
+pub struct WebcamOptions {
+    pub device: String,                // DirectShow video device (from get_video_devices)
+    pub corner: Option<String>,        // "top-left", "top-right", "bottom-left" or "bottom-right" (default)
+    pub size_percent: Option<u32>,     // Webcam width as a percentage of the recording width, 20 by default
+    pub circle: Option<bool>,          // Crop to a circle instead of a rectangle
+}
+
+const WEBCAM_CORNERS: [&str; 4] = ["top-left", "top-right", "bottom-left", "bottom-right"];
+
+impl WebcamOptions {
+    fn validate(&self) -> Result<(), String> {
+        if self.device.is_empty() {
+            return Err("Webcam device is empty".to_string());
+        }
+        if let Some(corner) = &self.corner {
+            if !WEBCAM_CORNERS.contains(&corner.as_str()) {
+                return Err(format!("Invalid webcam corner \"{}\": expected one of {}", corner, WEBCAM_CORNERS.join(", ")));
+            }
+        }
+        if let Some(size) = self.size_percent {
+            if !(5..=50).contains(&size) {
+                return Err(format!("Invalid webcam size {}%: must be between 5 and 50", size));
+            }
+        }
+        Ok(())
+    }
 }
 
+// Video encoding and container arguments for the recording, up to and
+// including the output path:
+// - codec/preset/crf from RecordingOptions (NVENC maps them to its own flags)
+// - HEVC tagged hvc1 so Windows and browsers recognize it in MP4
+// - MP4 written fragmented: an empty moov up front and a fragment per
+//   keyframe, so a recording cut short by a crash or a killed app is still
+//   playable up to the last fragment instead of missing its moov atom
+fn recording_output_args(flags: &CaptureFlags) -> Vec<String> {
+    let mut args = vec![
+        "-c:v".to_string(), flags.codec.clone(),
+        "-pix_fmt".to_string(), "yuv420p".to_string(),
+    ];
+    if flags.codec == "h264_nvenc" {
+        // NVENC has its own presets (p1-p7) and constant-quality mode
+        args.extend(["-preset".to_string(), "p1".to_string()]);
+        if let Some(crf) = flags.crf {
+            args.extend(["-rc".to_string(), "vbr".to_string(), "-cq".to_string(), crf.to_string()]);
+        }
+    } else {
+        args.extend(["-preset".to_string(), flags.preset.clone()]);
+        if let Some(crf) = flags.crf {
+            args.extend(["-crf".to_string(), crf.to_string()]);
+        }
+    }
+    if flags.codec == "libx265" {
+        args.extend(["-tag:v".to_string(), "hvc1".to_string()]);
+    }
+    if flags.filename.to_lowercase().ends_with(".mp4") {
+        args.extend([
+            "-movflags".to_string(),
+            "+frag_keyframe+empty_moov+default_base_moof".to_string(),
+        ]);
+    }
+    args.extend([
+        "-r".to_string(), flags.fps.clone(),
+        "-y".to_string(),
+        flags.filename.clone(),
+    ]);
+    args
+}
+
+// Filter chain that scales the webcam input, optionally masks it to a circle,
+// and overlays it in a corner of the screen video, labeled [vout]
+fn webcam_overlay_filter(webcam: &WebcamOptions, input_index: usize, frame_width: u32) -> String {
+    let cam_width = (frame_width * webcam.size_percent.unwrap_or(20) / 100).max(2) & !1;
+    let margin = (frame_width / 50) & !1;
+    
+    let shape = if webcam.circle.unwrap_or(false) {
+        // Square crop from the center, then zero alpha outside the inscribed circle
+        format!(
+            "crop='min(iw,ih)':'min(iw,ih)',scale={w}:{w},format=yuva420p,\
+             geq=lum='p(X,Y)':cb='p(X,Y)':cr='p(X,Y)':a='if(lte(hypot(X-W/2,Y-H/2),W/2),255,0)'",
+            w = cam_width
+        )
+    } else {
+        format!("scale={}:-2", cam_width)
+    };
+    
+    let (x, y) = match webcam.corner.as_deref().unwrap_or("bottom-right") {
+        "top-left" => (format!("{}", margin), format!("{}", margin)),
+        "top-right" => (format!("main_w-overlay_w-{}", margin), format!("{}", margin)),
+        "bottom-left" => (format!("{}", margin), format!("main_h-overlay_h-{}", margin)),
+        _ => (format!("main_w-overlay_w-{}", margin), format!("main_h-overlay_h-{}", margin)),
+    };
+    
+    format!("[{}:v]{}[cam];[0:v][cam]overlay=x={}:y={}[vout]", input_index, shape, x, y)
+}
+
+// Sleep for the given duration, waking early if the recording is stopped.
+// Returns false if it was stopped.
+fn wait_while_recording(signal: &AtomicBool, duration: Duration) -> bool {
+    let deadline = Instant::now() + duration;
+    while Instant::now() < deadline {
+        if !signal.load(Ordering::Relaxed) {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    signal.load(Ordering::Relaxed)
+}
+
+// Frame rates the recorder offers; anything else is rejected instead of
+// being replaced with a default
+const SUPPORTED_FPS: [u32; 3] = [24, 30, 60];
+
+fn parse_fps(fps: &str) -> Result<u32, String> {
+    let value: u32 = fps.trim().parse().map_err(|_| format!("Invalid frame rate: {}", fps))?;
+    if !SUPPORTED_FPS.contains(&value) {
+        return Err(format!("Unsupported frame rate {} (supported: {:?})", value, SUPPORTED_FPS));
+    }
+    Ok(value)
+}
+
+// Hz a display may fall short of a frame rate and still be counted as
+// sustaining it (59 Hz for 60 fps)
+const REFRESH_RATE_TOLERANCE: u32 = 2;
+
+// Windows Graphics Capture delivers at most one frame per display refresh,
+// so a higher rate would only duplicate frames. Lower it to the fastest
+// supported rate the display can keep up with
+fn sustainable_fps(requested: u32, refresh_rate: Option<u32>) -> u32 {
+    // Refresh rates are reported as whole numbers, so a 59.94 Hz panel says
+    // 59; allow a little slack before treating a rate as too slow
+    let sustains = |rate: u32, fps: u32| rate + REFRESH_RATE_TOLERANCE >= fps;
+    match refresh_rate.filter(|rate| *rate > 0 && !sustains(*rate, requested)) {
+        Some(rate) => SUPPORTED_FPS
+            .iter()
+            .copied()
+            .filter(|fps| sustains(rate, *fps))
+            .max()
+            .unwrap_or(SUPPORTED_FPS[0]),
+        None => requested,
+    }
+}
+
+// Returns the frame rate actually used, which may be lower than requested
 #[tauri::command]
-pub fn start_recording(state: State<'_, RecorderState>, filename: String, fps: String, target: Option<RecordTarget>) -> Result<(), String> {
+pub fn start_recording(
+    app: AppHandle,
+    state: State<'_, RecorderState>,
+    filename: String,
+    fps: String,
+    target: Option<RecordTarget>,
+    capture_system_audio: Option<bool>,
+    mic_device: Option<String>,
+    options: Option<RecordingOptions>,
+    countdown_secs: Option<u32>,
+    webcam: Option<WebcamOptions>,
+    multi_click_ms: Option<u64>,
+    zoom_factor: Option<f64>,
+) -> Result<u32, String> {
     if state.is_recording.load(Ordering::Relaxed) {
         return Err("Already recording".to_string());
     }
+    
+    let multi_click_ms = multi_click_ms.unwrap_or(DEFAULT_MULTI_CLICK_MS);
+    if !MULTI_CLICK_MS_RANGE.contains(&multi_click_ms) {
+        return Err(format!(
+            "Double-click threshold must be between {} and {} ms, got {}",
+            MULTI_CLICK_MS_RANGE.start(), MULTI_CLICK_MS_RANGE.end(), multi_click_ms
+        ));
+    }
+    let zoom_factor = check_zoom_factor(zoom_factor.unwrap_or(DEFAULT_ZOOM_FACTOR))?;
+    
+    let requested_fps = parse_fps(&fps)?;
+    
+    let options = options.unwrap_or_default();
+    options.validate()?;
+    if let Some(webcam) = &webcam {
+        webcam.validate()?;
+    }
+    let codec = options.resolve_codec();
+    let preset = options.preset.unwrap_or_else(|| "ultrafast".to_string());
+    let crf = options.crf;
+    
+    // FFmpeg can only record system audio through a DirectShow loopback
+    // device. A missing one shouldn't cost the user the recording, so fall
+    // back to video-only capture and send "audio-unavailable" once it starts
+    let system_audio_device = if capture_system_audio.unwrap_or(false) {
+        let device = find_loopback_audio_device();
+        if device.is_none() {
+            eprintln!("No system audio loopback device found (enable \"Stereo Mix\" in Windows sound settings), recording video only");
+        }
+        device
+    } else {
+        None
+    };
+    let system_audio_missing = capture_system_audio.unwrap_or(false) && system_audio_device.is_none();
+    
+    // Check a window target before committing, so a stale HWND is reported
+    // to the caller instead of failing silently on the capture thread
+    if let Some(t) = target.as_ref().filter(|t| t.target_type == "window") {
+        let hwnd = t.id.ok_or("Window target requires an id")? as isize;
+        if Window::from_raw_hwnd(hwnd as *mut std::ffi::c_void).rect().is_err() {
+            return Err("Selected window is no longer available".to_string());
+        }
+    }
    
     // Clear previous events and initialize tracking
     CLICK_EVENTS.lock().unwrap().clear();
@@ -470,97 +1376,215 @@ pub fn start_recording(state: State<'_, RecorderState>, filename: String, fps: S
     *LAST_CLICK.lock().unwrap() = None;
     LAST_TWO_CLICKS.lock().unwrap().clear();
     *LAST_ZOOM_TRIGGER.lock().unwrap() = None;
-    *LAST_CURSOR_SAMPLE.lock().unwrap() = None;
-    *RECORDING_START_TIME.lock().unwrap() = Some(Instant::now());
+    *LAST_RECORDING_STATS.lock().unwrap() = None;
+    // Set by the capture handler when the first frame arrives
+    *RECORDING_START_TIME.lock().unwrap() = None;
+    *PAUSE_STARTED.lock().unwrap() = None;
+    
+    // Monitor to capture when not recording a window: primary unless one was picked.
+    // Displays can be unplugged between listing and recording, so an index
+    // that no longer exists falls back to the primary monitor
+    let picked_monitor = target
+        .as_ref()
+        .filter(|t| t.target_type == "monitor" || t.target_type == "region")
+        .and_then(|t| t.monitor_index)
+        .and_then(|index| match Monitor::from_index(index) {
+            Ok(monitor) => Some(monitor),
+            Err(e) => {
+                eprintln!("Monitor {} not found ({:?}), recording the primary monitor", index, e);
+                None
+            }
+        });
+    let capture_monitor = match picked_monitor {
+        Some(monitor) => monitor,
+        None => Monitor::primary().map_err(|e| format!("No primary monitor: {:?}", e))?,
+    };
+    let capture_origin = monitor_origin(&app, &capture_monitor);
+    
+    // A window is limited by the display it is on
+    let refresh_rate = match target.as_ref().filter(|t| t.target_type == "window").and_then(|t| t.id) {
+        Some(hwnd) => Window::from_raw_hwnd(hwnd as isize as *mut std::ffi::c_void)
+            .monitor()
+            .and_then(|monitor| monitor.refresh_rate().ok()),
+        None => capture_monitor.refresh_rate().ok(),
+    };
+    let fps_value = sustainable_fps(requested_fps, refresh_rate);
+    if fps_value != requested_fps {
+        println!("Display refreshes at {:?} Hz, recording at {} FPS instead of {}", refresh_rate, fps_value, requested_fps);
+    }
+    let fps = fps_value.to_string();
     
     // Get screen size for coordinate normalization
-    let primary_monitor = Monitor::primary().expect("No primary monitor");
-    let screen_w = primary_monitor.width().unwrap_or(1920);
-    let screen_h = primary_monitor.height().unwrap_or(1080);
+    let screen_w = capture_monitor.width().unwrap_or(1920);
+    let screen_h = capture_monitor.height().unwrap_or(1080);
     *SCREEN_SIZE.lock().unwrap() = (screen_w, screen_h);
     
+    // Region recording crops the monitor capture to the selected rectangle
+    let region = match &target {
+        Some(t) if t.target_type == "region" => Some(clamp_region(t, screen_w, screen_h)?),
+        _ => None,
+    };
+    
+    // Make sure the drive has room before starting, since FFmpeg only fails
+    // once it is full. Window sizes aren't known yet, so estimate with the
+    // whole screen, which is an upper bound
+    let (estimate_w, estimate_h) = region.map(|(_, _, w, h)| (w, h)).unwrap_or((screen_w, screen_h));
+    let bytes_per_second = estimated_bytes_per_second(estimate_w, estimate_h, fps_value as f64);
+    match free_disk_space(&output_dir(&filename)) {
+        Ok(free_bytes) if bytes_per_second > 0 => {
+            let seconds_left = free_bytes / bytes_per_second;
+            if seconds_left < MIN_RECORDING_SECONDS {
+                return Err(format!(
+                    "Not enough disk space: {} MB free, enough for about {}s of recording",
+                    free_bytes / 1_000_000, seconds_left
+                ));
+            }
+            println!("{} MB free, enough for about {} minutes of recording", free_bytes / 1_000_000, seconds_left / 60);
+        }
+        Ok(_) => {}
+        // Not being able to check shouldn't block recording
+        Err(e) => eprintln!("{}", e),
+    }
+    
+    state.is_paused.store(false, Ordering::Relaxed);
+    state.multi_click_ms.store(multi_click_ms, Ordering::Relaxed);
+    *state.zoom_factor.lock().unwrap() = zoom_factor;
+    state.is_capturing_audio.store(system_audio_device.is_some() || mic_device.is_some(), Ordering::Relaxed);
+    state.is_capturing_webcam.store(webcam.is_some(), Ordering::Relaxed);
     state.is_recording.store(true, Ordering::Relaxed);
     let signal = state.is_recording.clone();
-    
-    // Spawn mouse listener in background
-    spawn_mouse_listener_v2(signal.clone());
+    let pause_signal = state.is_paused.clone();
+    let counting_down = state.is_counting_down.clone();
+    let multi_click_ms = state.multi_click_ms.clone();
+    let zoom_factor = state.zoom_factor.clone();
+    let countdown_secs = countdown_secs.unwrap_or(0);
+    counting_down.store(countdown_secs > 0, Ordering::Relaxed);
    
     thread::spawn(move || {
-        // Determine capture source based on target
-        let capture_result = match &target {
-            Some(t) if t.target_type == "window" && t.id.is_some() => {
-                // Window capture
-                let hwnd = t.id.unwrap() as isize as *mut std::ffi::c_void;
-                let window = Window::from_raw_hwnd(hwnd);
-                
-                println!("Capturing window: {:?}", window.title());
-                
-                // Get window dimensions and position
-                let rect = window.rect().map_err(|e| format!("Failed to get window rect: {:?}", e))?;
-                let width = (rect.right - rect.left) as u32;
-                let height = (rect.bottom - rect.top) as u32;
-                
-                // Set capture bounds for coordinate transformation
-                // Window position on screen + window dimensions
-                *CAPTURE_BOUNDS.lock().unwrap() = (rect.left, rect.top, width, height);
-                println!("Capture bounds set: ({}, {}, {}x{})", rect.left, rect.top, width, height);
-                
-                let flags = CaptureFlags {
-                    filename,
-                    stop_signal: signal.clone(),
-                    width,
-                    height,
-                    fps,
-                };
-
-                let settings = Settings::new(
-                    window,
-                    CursorCaptureSettings::WithoutCursor,  // Hide system cursor - we render our own
-                    DrawBorderSettings::Default,
-                    SecondaryWindowSettings::Default,
-                    MinimumUpdateIntervalSettings::Default,
-                    DirtyRegionSettings::Default,
-                    ColorFormat::Bgra8,
-                    flags,
-                );
-
-                CaptureHandler::start(settings)
+        // Count down before capturing. Emits the seconds left (3, 2, 1) and
+        // then 0 when capture starts. stop_recording during the countdown
+        // clears the recording flag, so FFmpeg is never started.
+        for remaining in (1..=countdown_secs).rev() {
+            let _ = app.emit("record-countdown", remaining);
+            if !wait_while_recording(&signal, Duration::from_secs(1)) {
+                println!("Recording cancelled during countdown");
+                counting_down.store(false, Ordering::Relaxed);
+                return;
             }
-            _ => {
-                // Monitor capture (default)
-                let primary_monitor = Monitor::primary().expect("No primary monitor");
-                let width = primary_monitor.width().expect("Failed to get monitor width");
-                let height = primary_monitor.height().expect("Failed to get monitor height");
-                
-                // Set capture bounds for coordinate transformation
-                // Full screen: origin at (0,0)
-                *CAPTURE_BOUNDS.lock().unwrap() = (0, 0, width, height);
-                println!("Capture bounds set: (0, 0, {}x{})", width, height);
-                
-                println!("Capturing primary monitor: {}x{}", width, height);
-                   
-                let flags = CaptureFlags {
-                    filename,
-                    stop_signal: signal.clone(),
-                    width,
-                    height,
-                    fps,
-                };
+        }
+        if countdown_secs > 0 {
+            let _ = app.emit("record-countdown", 0);
+        }
+        counting_down.store(false, Ordering::Relaxed);
+        if system_audio_missing {
+            let _ = app.emit(
+                "audio-unavailable",
+                "No system audio loopback device: enable \"Stereo Mix\" in Windows sound settings. Recording video only",
+            );
+        }
+        
+        // Start the mouse listener in background (no-op if already running)
+        spawn_mouse_listener_v2(signal.clone(), pause_signal.clone(), multi_click_ms, zoom_factor);
+        
+        // Errors end the recording instead of panicking the capture thread
+        let capture_result = (|| -> Result<(), String> {
+            // Determine capture source based on target
+            match &target {
+                Some(t) if t.target_type == "window" && t.id.is_some() => {
+                    // Window capture
+                    let raw_hwnd = t.id.unwrap() as isize;
+                    let window = Window::from_raw_hwnd(raw_hwnd as *mut std::ffi::c_void);
+                    
+                    println!("Capturing window: {:?}", window.title());
+                    
+                    // Record the client area only (no title bar or borders)
+                    let client = window_client_area(raw_hwnd)?;
+                    let (width, height) = (client.width, client.height);
+                    
+                    // Set capture bounds for coordinate transformation
+                    // Client area position on screen + client dimensions
+                    *CAPTURE_BOUNDS.lock().unwrap() = (client.screen_x, client.screen_y, width, height);
+                    println!("Capture bounds set: ({}, {}, {}x{})", client.screen_x, client.screen_y, width, height);
+                    
+                    let flags = CaptureFlags {
+                        filename,
+                        stop_signal: signal.clone(),
+                        pause_signal: pause_signal.clone(),
+                        width,
+                        height,
+                        fps,
+                        system_audio_device,
+                        mic_device,
+                        target_window: Some(raw_hwnd),
+                        crop_origin: (client.offset_x, client.offset_y),
+                        app: app.clone(),
+                        codec,
+                        preset,
+                        crf,
+                        webcam,
+                    };
+
+                    let settings = Settings::new(
+                        window,
+                        CursorCaptureSettings::WithoutCursor,  // Hide system cursor - we render our own
+                        DrawBorderSettings::Default,
+                        SecondaryWindowSettings::Default,
+                        MinimumUpdateIntervalSettings::Default,
+                        DirtyRegionSettings::Default,
+                        ColorFormat::Bgra8,
+                        flags,
+                    );
 
-                let settings = Settings::new(
-                    primary_monitor,
-                    CursorCaptureSettings::WithoutCursor,  // Hide system cursor - we render our own
-                    DrawBorderSettings::Default,
-                    SecondaryWindowSettings::Default,
-                    MinimumUpdateIntervalSettings::Default,
-                    DirtyRegionSettings::Default,
-                    ColorFormat::Bgra8,
-                    flags,
-                );
-
-                CaptureHandler::start(settings)
+                    CaptureHandler::start(settings).map_err(|e| format!("{:?}", e))
+                }
+                _ => {
+                    // Monitor capture (default), optionally cropped to a region
+                    let monitor_w = capture_monitor.width().map_err(|e| format!("Failed to get monitor width: {:?}", e))?;
+                    let monitor_h = capture_monitor.height().map_err(|e| format!("Failed to get monitor height: {:?}", e))?;
+                    let (crop_x, crop_y, width, height) = region.unwrap_or((0, 0, monitor_w, monitor_h));
+                    
+                    // Set capture bounds for coordinate transformation
+                    // Monitor's desktop position plus the region offset (zero for full screen)
+                    let (origin_x, origin_y) = capture_origin;
+                    let (bounds_x, bounds_y) = (origin_x + crop_x as i32, origin_y + crop_y as i32);
+                    *CAPTURE_BOUNDS.lock().unwrap() = (bounds_x, bounds_y, width, height);
+                    println!("Capture bounds set: ({}, {}, {}x{})", bounds_x, bounds_y, width, height);
+                    
+                    println!("Capturing monitor {:?}: {}x{}", capture_monitor.name(), width, height);
+                       
+                    let flags = CaptureFlags {
+                        filename,
+                        stop_signal: signal.clone(),
+                        pause_signal: pause_signal.clone(),
+                        width,
+                        height,
+                        fps,
+                        system_audio_device,
+                        mic_device,
+                        target_window: None,
+                        crop_origin: (crop_x, crop_y),
+                        app: app.clone(),
+                        codec,
+                        preset,
+                        crf,
+                        webcam,
+                    };
+
+                    let settings = Settings::new(
+                        capture_monitor,
+                        CursorCaptureSettings::WithoutCursor,  // Hide system cursor - we render our own
+                        DrawBorderSettings::Default,
+                        SecondaryWindowSettings::Default,
+                        MinimumUpdateIntervalSettings::Default,
+                        DirtyRegionSettings::Default,
+                        ColorFormat::Bgra8,
+                        flags,
+                    );
+
+                    CaptureHandler::start(settings).map_err(|e| format!("{:?}", e))
+                }
             }
-        };
+        })();
 
         match capture_result {
             Ok(_) => println!("Recording finished successfully"),
@@ -568,10 +1592,9 @@ pub fn start_recording(state: State<'_, RecorderState>, filename: String, fps: S
         }
 
         signal.store(false, Ordering::Relaxed);
-        Ok::<(), String>(())
     });
 
-    Ok(())
+    Ok(fps_value)
 }
 
 #[tauri::command]
@@ -580,7 +1603,61 @@ pub fn stop_recording(state: State<'_, RecorderState>) -> Result<(), String> {
         return Err("Not recording".to_string());
     }
     state.is_recording.store(false, Ordering::Relaxed);
+    state.is_paused.store(false, Ordering::Relaxed);
     *RECORDING_START_TIME.lock().unwrap() = None;
+    *PAUSE_STARTED.lock().unwrap() = None;
+    Ok(())
+}
+
+// Change the zoom factor of triple-click zooms, also mid-recording (clicks
+// already recorded keep theirs)
+#[tauri::command]
+pub fn set_zoom_factor(state: State<'_, RecorderState>, factor: f64) -> Result<(), String> {
+    *state.zoom_factor.lock().unwrap() = check_zoom_factor(factor)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn pause_recording(state: State<'_, RecorderState>) -> Result<(), String> {
+    if !state.is_recording.load(Ordering::Relaxed) {
+        return Err("Not recording".to_string());
+    }
+    if state.is_counting_down.load(Ordering::Relaxed) {
+        return Err("Cannot pause during the countdown".to_string());
+    }
+    // The audio device keeps running in FFmpeg, so pausing would desync it
+    if state.is_capturing_audio.load(Ordering::Relaxed) {
+        return Err("Pausing is not supported while capturing audio".to_string());
+    }
+    // Same for the webcam: it keeps streaming while the screen is held
+    if state.is_capturing_webcam.load(Ordering::Relaxed) {
+        return Err("Pausing is not supported while recording the webcam".to_string());
+    }
+    if state.is_paused.swap(true, Ordering::Relaxed) {
+        return Err("Already paused".to_string());
+    }
+    *PAUSE_STARTED.lock().unwrap() = Some(Instant::now());
+    Ok(())
+}
+
+#[tauri::command]
+pub fn resume_recording(state: State<'_, RecorderState>) -> Result<(), String> {
+    if !state.is_recording.load(Ordering::Relaxed) {
+        return Err("Not recording".to_string());
+    }
+    if !state.is_paused.load(Ordering::Relaxed) {
+        return Err("Not paused".to_string());
+    }
+    // Shift the recording clock forward by the paused time, so the video,
+    // cursor and click timelines all continue from where they left off
+    if let Some(pause_started) = PAUSE_STARTED.lock().unwrap().take() {
+        let paused_for = pause_started.elapsed();
+        if let Some(start) = RECORDING_START_TIME.lock().unwrap().as_mut() {
+            *start += paused_for;
+        }
+        println!("Resumed after {:.1}s pause", paused_for.as_secs_f64());
+    }
+    state.is_paused.store(false, Ordering::Relaxed);
     Ok(())
 }
 
@@ -591,10 +1668,192 @@ pub fn get_recorded_clicks() -> Vec<ClickEvent> {
     events.clone()
 }
 
+// Frame accounting of the last recording, None until its encoder has
+// finished (call after stopping recording)
+#[tauri::command]
+pub fn get_recording_stats() -> Option<RecordingStats> {
+    LAST_RECORDING_STATS.lock().unwrap().clone()
+}
+
+// Interval of the cursor timeline returned to the editor (60Hz)
+const CURSOR_SAMPLE_INTERVAL_MS: u64 = 16;
+
+// Resample raw mouse-move events onto an evenly spaced timeline. rdev fires
+// on every move, so between two events the cursor sat still: each sample
+// holds the latest event at or before it rather than blending toward the
+// next one (which would invent a slow drift across idle stretches).
+fn resample_cursor_positions(raw: &[CursorPosition]) -> Vec<CursorPosition> {
+    let (first, last) = match (raw.first(), raw.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Vec::new(),
+    };
+
+    let mut result = Vec::new();
+    let mut index = 0;
+    let mut timestamp_ms = first.timestamp_ms;
+    while timestamp_ms <= last.timestamp_ms {
+        while index + 1 < raw.len() && raw[index + 1].timestamp_ms <= timestamp_ms {
+            index += 1;
+        }
+        result.push(CursorPosition {
+            timestamp_ms,
+            x: raw[index].x,
+            y: raw[index].y,
+        });
+        timestamp_ms += CURSOR_SAMPLE_INTERVAL_MS;
+    }
+    // Keep the final position even if it falls between two samples
+    if result.last().map(|p| p.timestamp_ms) != Some(last.timestamp_ms) {
+        result.push(last.clone());
+    }
+    result
+}
+
 // Get recorded cursor positions (call after stopping recording)
 #[tauri::command]
 pub fn get_cursor_positions() -> Vec<CursorPosition> {
     let positions = CURSOR_POSITIONS.lock().unwrap();
-    println!("Returning {} cursor positions", positions.len());
-    positions.clone()
-}
\ No newline at end of file
+    let timeline = resample_cursor_positions(&positions);
+    println!("Returning {} cursor positions ({} raw moves)", timeline.len(), positions.len());
+    timeline
+}
+
+// A stretch of the recording with no cursor movement and no clicks
+#[derive(serde::Serialize, Clone, Debug)]
+pub struct IdleRange {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+const DEFAULT_IDLE_MIN_SECS: f64 = 3.0;
+const DEFAULT_IDLE_JITTER_PX: f64 = 3.0;
+// Activity kept on each side of a cut, so it doesn't start or end abruptly
+const IDLE_MARGIN_MS: u64 = 500;
+
+// Times (ms) at which the user did something: every click, and every cursor
+// move that strays more than `jitter_px` from where the cursor last settled
+fn activity_times(positions: &[CursorPosition], clicks: &[ClickEvent], jitter_px: f64) -> Vec<u64> {
+    let (_, _, cap_w, cap_h) = *CAPTURE_BOUNDS.lock().unwrap();
+    let mut times: Vec<u64> = clicks.iter().map(|click| click.timestamp_ms).collect();
+    let mut anchor: Option<&CursorPosition> = None;
+    for position in positions {
+        let moved = match anchor {
+            Some(a) => {
+                let dx = (position.x - a.x) * cap_w as f64;
+                let dy = (position.y - a.y) * cap_h as f64;
+                (dx * dx + dy * dy).sqrt() > jitter_px
+            }
+            None => true,
+        };
+        if moved {
+            times.push(position.timestamp_ms);
+            anchor = Some(position);
+        }
+    }
+    times.sort_unstable();
+    times
+}
+
+// Suggest ranges to cut from the last recording: gaps longer than
+// `min_idle_secs` between bits of activity (including before the first and,
+// if `duration_ms` is given, after the last). Cut ranges are ready to invert
+// into keep_segments for trim_video_segments
+#[tauri::command]
+pub fn detect_idle_ranges(
+    duration_ms: Option<u64>,
+    min_idle_secs: Option<f64>,
+    jitter_px: Option<f64>,
+) -> Vec<IdleRange> {
+    let min_idle_ms = (min_idle_secs.unwrap_or(DEFAULT_IDLE_MIN_SECS).max(0.0) * 1000.0) as u64;
+    let jitter_px = jitter_px.unwrap_or(DEFAULT_IDLE_JITTER_PX).max(0.0);
+    let positions = CURSOR_POSITIONS.lock().unwrap().clone();
+    let clicks = CLICK_EVENTS.lock().unwrap().clone();
+
+    let mut boundaries = vec![0];
+    boundaries.extend(activity_times(&positions, &clicks, jitter_px));
+    if let Some(duration_ms) = duration_ms {
+        boundaries.push(duration_ms);
+    }
+
+    let ranges: Vec<IdleRange> = boundaries
+        .windows(2)
+        .filter(|pair| pair[1].saturating_sub(pair[0]) >= min_idle_ms.max(2 * IDLE_MARGIN_MS + 1))
+        .map(|pair| IdleRange {
+            start_ms: pair[0] + IDLE_MARGIN_MS,
+            end_ms: pair[1] - IDLE_MARGIN_MS,
+        })
+        .collect();
+    println!("Found {} idle ranges over {}s", ranges.len(), min_idle_ms as f64 / 1000.0);
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sustainable_fps_accepts_fractional_refresh_rates() {
+        // 59.94 Hz panels report 59
+        assert_eq!(sustainable_fps(60, Some(59)), 60);
+        assert_eq!(sustainable_fps(60, Some(60)), 60);
+        assert_eq!(sustainable_fps(60, Some(144)), 60);
+        assert_eq!(sustainable_fps(60, None), 60);
+    }
+
+    #[test]
+    fn sustainable_fps_lowers_the_rate_for_slow_displays() {
+        assert_eq!(sustainable_fps(60, Some(50)), 30);
+        assert_eq!(sustainable_fps(60, Some(29)), 30);
+        assert_eq!(sustainable_fps(30, Some(25)), 24);
+    }
+
+    fn region(x: i32, y: i32, width: u32, height: u32) -> RecordTarget {
+        RecordTarget {
+            target_type: "region".to_string(),
+            id: None,
+            monitor_index: None,
+            x: Some(x),
+            y: Some(y),
+            width: Some(width),
+            height: Some(height),
+        }
+    }
+
+    #[test]
+    fn clamp_region_keeps_the_region_on_the_monitor() {
+        let cases = [
+            (region(100, 50, 640, 480), Ok((100, 50, 640, 480))),
+            // Odd sizes round down for yuv420p
+            (region(0, 0, 641, 481), Ok((0, 0, 640, 480))),
+            // Hanging off the top-left or bottom-right edge
+            (region(-100, -100, 400, 300), Ok((0, 0, 300, 200))),
+            (region(1800, 1000, 400, 300), Ok((1800, 1000, 120, 80))),
+            (region(2000, 0, 100, 100), Err(())),
+            (region(0, 0, 1, 100), Err(())),
+        ];
+        for (target, expected) in cases {
+            assert_eq!(clamp_region(&target, 1920, 1080).map_err(|_| ()), expected, "{:?}", target);
+        }
+        let mut missing = region(0, 0, 100, 100);
+        missing.width = None;
+        assert!(clamp_region(&missing, 1920, 1080).is_err());
+    }
+
+    fn position(timestamp_ms: u64, x: f64) -> CursorPosition {
+        CursorPosition { timestamp_ms, x, y: 0.5 }
+    }
+
+    #[test]
+    fn resample_cursor_positions_holds_the_last_move() {
+        assert!(resample_cursor_positions(&[]).is_empty());
+
+        let raw = [position(0, 0.1), position(20, 0.2), position(40, 0.3)];
+        let samples: Vec<(u64, f64)> = resample_cursor_positions(&raw)
+            .iter()
+            .map(|p| (p.timestamp_ms, p.x))
+            .collect();
+        // 16ms steps hold the latest move at or before them, and the final
+        // move is kept even though it falls between two steps
+        assert_eq!(samples, vec![(0, 0.1), (16, 0.1), (32, 0.2), (40, 0.3)]);
+    }
+}
@@ -1,7 +1,7 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWindow, LogicalSize } from "@tauri-apps/api/window";
-// import { listen } from "@tauri-apps/api/event"; // Uncomment for preview feature
+import { listen } from "@tauri-apps/api/event";
 import VideoEditor from "./VideoEditor";
 
 interface WindowInfo {
@@ -9,13 +9,25 @@ interface WindowInfo {
   title: string;
 }
 
+interface MonitorInfo {
+  index: number;  // One-based
+  name: string;
+  width: number;
+  height: number;
+  x: number;
+  y: number;
+  is_primary: boolean;
+}
+
 // Click event from backend
 interface ClickEvent {
   timestamp_ms: number;
   x: number;
   y: number;
+  button: 'left' | 'right' | 'middle';
   is_double_click: boolean;
   is_triple_click: boolean;  // Zoom trigger
+  zoom_scale: number | null; // Zoom factor at the time of a triple-click
 }
 
 // Cursor position from backend
@@ -25,15 +37,36 @@ interface CursorPosition {
   y: number;
 }
 
+// Frame accounting of a finished recording from backend
+interface RecordingStats {
+  target_fps: number;
+  effective_fps: number;
+  frames_written: number;
+  frames_captured: number;
+  frames_duplicated: number;
+  frames_dropped: number;
+  encoder_bound: boolean;  // Dropped enough frames to warn about
+}
+
+// Zoom factors of triple-click zooms the record bar cycles through
+const ZOOM_FACTORS = [1.5, 2, 3];
+
+// Seconds of 3-2-1 countdown before capture starts
+const RECORD_COUNTDOWN_SECS = 3;
+
 function App() {
   const [isRecording, setIsRecording] = useState(false);
   const [status, setStatus] = useState("Ready");
   const [filename, setFilename] = useState("");
   const [windows, setWindows] = useState<WindowInfo[]>([]);
+  const [monitors, setMonitors] = useState<MonitorInfo[]>([]);
   const [selectedTarget, setSelectedTarget] = useState<string>("");
   const [selectedLabel, setSelectedLabel] = useState("Select App");
+  const [zoomFactor, setZoomFactor] = useState(ZOOM_FACTORS[0]);  // Triple-click zoom
   const [showSourceModal, setShowSourceModal] = useState(false);
   const [recordingTime, setRecordingTime] = useState(0);
+  const [countdown, setCountdown] = useState(0);
+  const [recordingFps, setRecordingFps] = useState(0);  // Rate the backend settled on
   const [editorMode, setEditorMode] = useState(false);
   const [lastRecordedFile, setLastRecordedFile] = useState("");
   const [recordedClicks, setRecordedClicks] = useState<ClickEvent[]>([]);
@@ -52,22 +85,59 @@ function App() {
         setStatus("Error: Could not get temp path");
       }
     };
+    // Recording and export both shell out to FFmpeg, so say so up front
+    const checkDependencies = async () => {
+      try {
+        const deps = await invoke<{
+          ffmpeg: { available: boolean; version: string | null };
+          ffprobe: { available: boolean; version: string | null };
+        }>("check_dependencies");
+        const missing = [
+          !deps.ffmpeg.available && "FFmpeg",
+          !deps.ffprobe.available && "ffprobe",
+        ].filter(Boolean);
+        if (missing.length > 0) {
+          setStatus(`${missing.join(" and ")} not found - install FFmpeg and add it to PATH`);
+        }
+      } catch (e) {
+        console.error("Failed to check dependencies", e);
+      }
+    };
     initTempPath();
+    checkDependencies();
     refreshWindows();
   }, []);
 
-  // Recording timer
+  // Recording timer, driven by the backend's count of recorded frames
   useEffect(() => {
-    let interval: ReturnType<typeof setInterval>;
-    if (isRecording) {
-      interval = setInterval(() => {
-        setRecordingTime((t) => t + 1);
-      }, 1000);
-    } else {
+    if (!isRecording) {
       setRecordingTime(0);
+      setCountdown(0);
+      return;
     }
-    return () => clearInterval(interval);
-  }, [isRecording]);
+    const unlisten = listen<{ elapsed_seconds: number; frames: number; dropped_frames: number }>("recording-tick", (event) => {
+      setRecordingTime(event.payload.elapsed_seconds);
+    });
+    // Seconds left before capture starts, 0 once it has
+    const unlistenCountdown = listen<number>("record-countdown", (event) => {
+      setCountdown(event.payload);
+      if (event.payload === 0) setStatus(`Recording at ${recordingFps} fps`);
+    });
+    // Sent when capture starts without the system audio that was asked for
+    const unlistenAudio = listen<string>("audio-unavailable", (event) => {
+      setStatus(event.payload);
+    });
+    // Sent once when the drive can only hold a few more minutes
+    const unlistenLowDisk = listen<{ free_bytes: number; seconds_left: number }>("low-disk", (event) => {
+      setStatus(`Low disk space: about ${Math.ceil(event.payload.seconds_left / 60)} min left`);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+      unlistenCountdown.then((fn) => fn());
+      unlistenLowDisk.then((fn) => fn());
+      unlistenAudio.then((fn) => fn());
+    };
+  }, [isRecording, recordingFps]);
 
   // Preview effect - uncomment to enable
   // useEffect(() => {
@@ -92,6 +162,12 @@ function App() {
     } catch (e) {
       console.error("Failed to list windows", e);
     }
+    try {
+      const mons = await invoke<MonitorInfo[]>("get_monitors");
+      setMonitors(mons);
+    } catch (e) {
+      console.error("Failed to list monitors", e);
+    }
   };
 
   const formatTime = (seconds: number) => {
@@ -102,6 +178,14 @@ function App() {
 
   const toggleRecording = async () => {
     try {
+      if (isRecording && countdown > 0) {
+        // Stopped during the 3-2-1: nothing was captured, so there is no
+        // file to open in the editor
+        await invoke("stop_recording");
+        setIsRecording(false);
+        setStatus("Recording cancelled");
+        return;
+      }
       if (isRecording) {
         setStatus("Saving...");
         await invoke("stop_recording");
@@ -113,6 +197,19 @@ function App() {
 
         setStatus("Saved!");
 
+        // Warn when the encoder was too slow for the chosen resolution/fps
+        try {
+          const stats = await invoke<RecordingStats | null>("get_recording_stats");
+          if (stats) {
+            console.log("Recording stats:", stats);
+            if (stats.encoder_bound) {
+              setStatus(`Saved, but the encoder couldn't keep up (${stats.effective_fps.toFixed(0)} of ${stats.target_fps} fps). Try a lower resolution or frame rate.`);
+            }
+          }
+        } catch (e) {
+          console.error("Failed to get recording stats:", e);
+        }
+
         // Fetch recorded click events
         try {
           const clicks = await invoke<ClickEvent[]>("get_recorded_clicks");
@@ -138,23 +235,42 @@ function App() {
         setEditorMode(true);
       } else {
         if (!selectedTarget) {
-          setStatus("Please select an app first");
+          setStatus("Please select a display or app first");
           return;
         }
         setStatus("Starting...");
-        const target = { type: "window", id: parseInt(selectedTarget) };
-
-        await invoke("start_recording", { filename, fps: "60", target });
+        // Monitors are selected as "monitor:<index>", windows by HWND
+        const target = selectedTarget.startsWith("monitor:")
+          ? { type: "monitor", monitor_index: parseInt(selectedTarget.slice("monitor:".length)) }
+          : { type: "window", id: parseInt(selectedTarget) };
+
+        // The display may not keep up with 60 fps, so use the rate the backend picked
+        const fps = await invoke<number>("start_recording", { filename, fps: "60", target, countdownSecs: RECORD_COUNTDOWN_SECS, zoomFactor });
+        setRecordingFps(fps);
+        // The first countdown event can fire before the listener is attached
+        setCountdown(RECORD_COUNTDOWN_SECS);
         setIsRecording(true);
-        setStatus("Recording");
+        setStatus("Get ready...");
       }
     } catch (error) {
       console.error(error);
-      setStatus("Error");
+      // Commands reject with a readable message (e.g. not enough disk space)
+      setStatus(typeof error === "string" ? error : "Error");
       setIsRecording(false);
     }
   };
 
+  // Next zoom factor; applies to triple-clicks from now on, also mid-recording
+  const cycleZoomFactor = async () => {
+    const next = ZOOM_FACTORS[(ZOOM_FACTORS.indexOf(zoomFactor) + 1) % ZOOM_FACTORS.length];
+    try {
+      await invoke("set_zoom_factor", { factor: next });
+      setZoomFactor(next);
+    } catch (e) {
+      console.error("Failed to set zoom factor:", e);
+    }
+  };
+
   const selectSource = (id: string, label: string) => {
     setSelectedTarget(id);
     setSelectedLabel(label);
@@ -330,6 +446,40 @@ function App() {
                   </button>
                 </div>
                 <div className="p-1 max-h-40 overflow-y-auto">
+                  {monitors.length > 0 && (
+                    <div className="flex flex-col border-b border-gray-100 pb-1 mb-1">
+                      {monitors.map((m) => {
+                        const id = `monitor:${m.index}`;
+                        const label = m.is_primary ? `${m.name} (Primary)` : m.name;
+                        return (
+                          <button
+                            type="button"
+                            key={id}
+                            onClick={() => handleSelectSource(id, label)}
+                            className={`flex items-center gap-1.5 px-2 py-1 rounded cursor-pointer text-left w-full border-none transition-colors ${selectedTarget === id
+                              ? "bg-blue-50 text-blue-700"
+                              : "bg-transparent hover:bg-gray-50 text-gray-700"
+                              }`}
+                          >
+                            <div className={`w-4 h-4 rounded flex items-center justify-center shrink-0 ${selectedTarget === id ? "bg-blue-100 text-blue-600" : "bg-gray-100 text-gray-500"}`}>
+                              <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" className="w-2 h-2">
+                                <rect x="2" y="3" width="20" height="14" rx="2" />
+                              </svg>
+                            </div>
+                            <span className="flex-1 text-[11px] font-medium truncate">
+                              {label}
+                            </span>
+                            <span className="text-[10px] text-gray-400">{m.width}x{m.height}</span>
+                            {selectedTarget === id && (
+                              <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="3" className="w-2.5 h-2.5 text-blue-500">
+                                <path d="M5 12l5 5L20 7" />
+                              </svg>
+                            )}
+                          </button>
+                        );
+                      })}
+                    </div>
+                  )}
                   {windows.length === 0 ? (
                     <div className="px-2 py-2.5 text-center text-gray-400 text-xs">
                       No windows found.
@@ -373,7 +523,7 @@ function App() {
             <div className="flex items-center gap-2 px-2.5 py-1 bg-red-50 rounded-lg border border-red-200">
               <span className="w-2 h-2 rounded-full bg-red-500 animate-pulse"></span>
               <span className="text-sm font-mono font-semibold text-red-600">
-                {formatTime(recordingTime)}
+                {countdown > 0 ? countdown : formatTime(recordingTime)}
               </span>
             </div>
           )}
@@ -381,6 +531,17 @@ function App() {
           {/* Spacer */}
           <div className="flex-1"></div>
 
+          {/* Triple-click zoom factor */}
+          <button
+            type="button"
+            onClick={(e) => { e.stopPropagation(); cycleZoomFactor(); }}
+            title="Zoom of triple-click zooms"
+            className="px-2 py-1 bg-gray-100 hover:bg-gray-200 rounded-lg text-gray-700 text-xs font-medium border-none cursor-pointer"
+            style={{ WebkitAppRegion: 'no-drag' } as React.CSSProperties}
+          >
+            {zoomFactor}x
+          </button>
+
           {/* Record/Stop Button */}
           <button
             type="button"
@@ -1,17 +1,22 @@
 import { useState, useRef, useEffect, useMemo } from "react";
 import { invoke, convertFileSrc } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import {
     VideoEditorProps,
     Effect,
     EffectType,
     CanvasSettings,
     ExportSettings,
+    ExportFinished,
     CursorSettings,
     EFFECT_CONFIG,
     DEFAULT_CANVAS_SETTINGS,
     DEFAULT_EXPORT_SETTINGS,
     DEFAULT_CURSOR_SETTINGS,
     rangesOverlap,
+    aspectRatioValue,
+    zoomEffectsFromClicks,
+    exportErrorMessage,
     formatTimeDetailed,
     generateTimeMarkers,
 } from "./components/editor";
@@ -46,6 +51,11 @@ function VideoEditor({ videoPath, onClose, clickEvents = [], cursorPositions = [
     // Export state
     const [isExporting, setIsExporting] = useState(false);
     const [exportStatus, setExportStatus] = useState("");
+    // Cancel stops frame rendering through the flag, and the encode through
+    // cancel_export once encode_frames has returned an id
+    const exportCancelledRef = useRef(false);
+    const exportIdRef = useRef<number | null>(null);
+    const [exportCancellable, setExportCancellable] = useState(false);  // Only canvas exports can be cancelled
 
     // Canvas settings (background, border radius, padding, click ripples)
     const [canvasSettings, setCanvasSettings] = useState<CanvasSettings>(DEFAULT_CANVAS_SETTINGS);
@@ -272,7 +282,7 @@ function VideoEditor({ videoPath, onClose, clickEvents = [], cursorPositions = [
                 startTime: adjustedStart,
                 endTime: adjustedStart + zoomDuration,
                 lane: 0,
-                scale: 1.5,
+                scale: click.zoom_scale ?? 1.5,
                 targetX: offsetTargetX,
                 targetY: offsetTargetY,
             };
@@ -332,6 +342,18 @@ function VideoEditor({ videoPath, onClose, clickEvents = [], cursorPositions = [
         setSelectedEffectId(newEffect.id);
     };
 
+    // Replace zoom effects with one per burst of recorded clicks
+    const zoomOnClicks = () => {
+        const clickZooms = zoomEffectsFromClicks(clickEvents, {
+            scale: 1.5,
+            easing: 'mellow',
+            mergeGapMs: 1500,
+            holdSecs: EFFECT_CONFIG.zoom.defaultDuration,
+        });
+        setEffects([...effects.filter(e => e.type !== 'zoom'), ...clickZooms]);
+        setSelectedEffectId(null);
+    };
+
     // Quick-add zoom at specific time (from timeline double-click)
     const quickAddZoom = (time: number) => {
         const config = EFFECT_CONFIG.zoom;
@@ -529,7 +551,57 @@ function VideoEditor({ videoPath, onClose, clickEvents = [], cursorPositions = [
     // CRITICAL: The video element must have access to its source file throughout
     // the entire export process, so we DON'T move/copy the file until we're done.
 
+    // Output size for the export resolution and aspect ratio settings
+    const getExportDimensions = (video: HTMLVideoElement): { width: number; height: number } => {
+        const videoWidth = video.videoWidth;
+        const videoHeight = video.videoHeight;
+
+        let exportWidth = videoWidth;
+        let exportHeight = videoHeight;
+
+        // Adjust for resolution setting
+        if (exportSettings.resolution === '720p') {
+            exportHeight = 720;
+            exportWidth = Math.round(720 * (videoWidth / videoHeight));
+        } else if (exportSettings.resolution === '1080p') {
+            exportHeight = 1080;
+            exportWidth = Math.round(1080 * (videoWidth / videoHeight));
+        } else if (exportSettings.resolution === '4k') {
+            exportHeight = 2160;
+            exportWidth = Math.round(2160 * (videoWidth / videoHeight));
+        }
+
+        // A different frame shape keeps the resolution as its short side,
+        // so 1080p at 9:16 is 1080x1920
+        if (canvasSettings.aspectRatio !== 'original') {
+            const aspect = aspectRatioValue(canvasSettings.aspectRatio, videoWidth, videoHeight);
+            const shortSide = Math.min(exportWidth, exportHeight);
+            if (aspect >= 1) {
+                exportHeight = shortSide;
+                exportWidth = Math.round(shortSide * aspect);
+            } else {
+                exportWidth = shortSide;
+                exportHeight = Math.round(shortSide / aspect);
+            }
+        }
+
+        // Ensure dimensions are even (required by many codecs)
+        exportWidth = Math.round(exportWidth / 2) * 2;
+        exportHeight = Math.round(exportHeight / 2) * 2;
+
+        return { width: exportWidth, height: exportHeight };
+    };
+
+    const handleCancelExport = async () => {
+        exportCancelledRef.current = true;
+        setExportStatus("Cancelling...");
+        if (exportIdRef.current !== null) {
+            await invoke("cancel_export", { id: exportIdRef.current });
+        }
+    };
+
     const handleExport = async () => {
+        exportCancelledRef.current = false;
         setIsExporting(true);
         setExportStatus("Preparing export...");
 
@@ -542,37 +614,17 @@ function VideoEditor({ videoPath, onClose, clickEvents = [], cursorPositions = [
 
         try {
             const timestamp = new Date().toISOString().replace(/[:.]/g, "-").slice(0, 19);
-            const finalName = `recording_${timestamp}_edited.mp4`;
+            const finalName = `recording_${timestamp}_edited.${exportSettings.format}`;
 
             // Get the Videos directory path (without moving the file yet!)
             const videosDir = await invoke<string>("get_videos_dir_path");
             const outputPath = `${videosDir}\\${finalName}`;
 
             // Determine export dimensions
-            const videoWidth = video.videoWidth;
-            const videoHeight = video.videoHeight;
-
-            let exportWidth = videoWidth;
-            let exportHeight = videoHeight;
-
-            // Adjust for resolution setting
-            if (exportSettings.resolution === '720p') {
-                exportHeight = 720;
-                exportWidth = Math.round(720 * (videoWidth / videoHeight));
-            } else if (exportSettings.resolution === '1080p') {
-                exportHeight = 1080;
-                exportWidth = Math.round(1080 * (videoWidth / videoHeight));
-            } else if (exportSettings.resolution === '4k') {
-                exportHeight = 2160;
-                exportWidth = Math.round(2160 * (videoWidth / videoHeight));
-            }
-
-            // Ensure dimensions are even (required by many codecs)
-            exportWidth = Math.round(exportWidth / 2) * 2;
-            exportHeight = Math.round(exportHeight / 2) * 2;
+            const { width: exportWidth, height: exportHeight } = getExportDimensions(video);
 
             const fps = 60;
-            const duration = trimEnd - trimStart;
+            const duration = (trimEnd - trimStart) / exportSettings.speed;
             const totalFrames = Math.ceil(duration * fps);
 
             setExportStatus(`Rendering 0/${totalFrames} frames...`);
@@ -582,19 +634,37 @@ function VideoEditor({ videoPath, onClose, clickEvents = [], cursorPositions = [
                 e.type === 'zoom' && e.startTime >= trimStart && e.endTime <= trimEnd
             );
 
-            if (!hasEffects && !cursorSettings.visible) {
-                // Fast path: no canvas rendering needed, just trim
+            // Padding, rounded corners, reshaping, vignette, fades and speed changes only exist in the canvas render
+            const hasFrameStyling = canvasSettings.paddingPercent > 0 || canvasSettings.borderRadius > 0
+                || canvasSettings.aspectRatio !== 'original' || canvasSettings.vignette
+                || exportSettings.fadeInSecs > 0 || exportSettings.fadeOutSecs > 0
+                || exportSettings.speed !== 1;
+
+            // Trimming writes H.264 MP4, so WebM always goes through the canvas export
+            if (!hasEffects && !hasFrameStyling && !cursorSettings.visible && exportSettings.format === 'mp4') {
+                // Fast path: no canvas rendering needed, just trim. Stream copy
+                // keeps it fast and at the recording's quality; the cut starts
+                // at the nearest keyframe before the trim handle
                 // For fast path, we CAN use the temp file directly since FFmpeg reads it once
                 setExportStatus("Exporting (fast mode)...");
-                await invoke("trim_video", {
-                    inputPath: videoPath,
-                    outputPath,
-                    startTime: trimStart,
-                    endTime: trimEnd,
+                const unlistenTrim = await listen<number>("trim-progress", (event) => {
+                    setExportStatus(`Exporting (fast mode)... ${Math.round(event.payload * 100)}%`);
                 });
+                try {
+                    await invoke("trim_video", {
+                        inputPath: videoPath,
+                        outputPath,
+                        startTime: trimStart,
+                        endTime: trimEnd,
+                        normalizeAudio: exportSettings.normalizeAudio,
+                    });
+                } finally {
+                    unlistenTrim();
+                }
             } else {
                 // Canvas-based export: render each frame from the ORIGINAL video
                 // The video element still has access to videoPath
+                setExportCancellable(true);
                 const { exportFrames } = await import('./components/editor/exportRenderer');
 
                 // Collect frames (we'll batch send to Rust)
@@ -610,10 +680,18 @@ function VideoEditor({ videoPath, onClose, clickEvents = [], cursorPositions = [
                         endTime: trimEnd,
                         effects,
                         cursorPositions,
+                        clickEvents,
                         cursorSettings,
                         canvasSettings,
+                        fadeInSecs: exportSettings.fadeInSecs,
+                        fadeOutSecs: exportSettings.fadeOutSecs,
+                        speed: exportSettings.speed,
                     },
                     async (frameData, frameIndex) => {
+                        if (exportCancelledRef.current) {
+                            throw { kind: 'cancelled' };
+                        }
+
                         // Convert Uint8Array to base64
                         const base64 = btoa(
                             frameData.reduce((data, byte) => data + String.fromCharCode(byte), '')
@@ -632,15 +710,59 @@ function VideoEditor({ videoPath, onClose, clickEvents = [], cursorPositions = [
 
                 setExportStatus(`Encoding ${frames.length} frames...`);
 
-                // Send frames to Rust for encoding - encode directly to Videos folder
-                await invoke("encode_frames", {
-                    outputPath,
-                    width: exportWidth,
-                    height: exportHeight,
-                    fps,
-                    quality: exportSettings.quality,
-                    framesBase64: frames,
+                // Backend streams encode progress as a 0-1 fraction
+                const unlistenProgress = await listen<number>("export-progress", (event) => {
+                    setExportStatus(`Encoding... ${Math.round(event.payload * 100)}%`);
+                });
+
+                // encode_frames returns an export id right away and reports
+                // the outcome as "export-finished" with that id
+                const finished = new Map<number, ExportFinished>();
+                let onFinished: ((result: ExportFinished) => void) | null = null;
+                const unlistenFinished = await listen<ExportFinished>("export-finished", (event) => {
+                    finished.set(event.payload.id, event.payload);
+                    onFinished?.(event.payload);
                 });
+
+                // Send frames to Rust for encoding - encode directly to Videos folder
+                try {
+                    const id = await invoke<number>("encode_frames", {
+                        outputPath,
+                        width: exportWidth,
+                        height: exportHeight,
+                        fps,
+                        quality: exportSettings.quality,
+                        format: exportSettings.format,
+                        maxFilesizeMb: exportSettings.maxFileSizeMb,
+                        encoder: exportSettings.format === 'mp4' ? exportSettings.encoder : 'software',
+                        // The rendered frames are video only; the soundtrack comes from the recording
+                        audio: {
+                            sourcePath: videoPath,
+                            startTime: trimStart,
+                            endTime: trimEnd,
+                            speed: exportSettings.speed,
+                            fadeIn: exportSettings.fadeInSecs,
+                            fadeOut: exportSettings.fadeOutSecs,
+                            normalize: exportSettings.normalizeAudio,
+                        },
+                        framesBase64: frames,
+                    });
+                    exportIdRef.current = id;
+                    // Cancel was pressed while the frames were being sent
+                    if (exportCancelledRef.current) {
+                        await invoke("cancel_export", { id });
+                    }
+                    const result = finished.get(id) ?? await new Promise<ExportFinished>((resolve) => {
+                        onFinished = (payload) => {
+                            if (payload.id === id) resolve(payload);
+                        };
+                    });
+                    if (result.error) throw result.error;
+                } finally {
+                    exportIdRef.current = null;
+                    unlistenProgress();
+                    unlistenFinished();
+                }
             }
 
             // Cleanup temp video file (now safe to delete since we're done reading it)
@@ -654,8 +776,10 @@ function VideoEditor({ videoPath, onClose, clickEvents = [], cursorPositions = [
             setTimeout(() => onClose(), 1500);
         } catch (error) {
             console.error("Export failed:", error);
-            setExportStatus("Export failed");
+            setExportStatus(exportCancelledRef.current ? "Export cancelled" : `Export failed: ${exportErrorMessage(error)}`);
             setIsExporting(false);
+        } finally {
+            setExportCancellable(false);
         }
 
     };
@@ -668,10 +792,18 @@ function VideoEditor({ videoPath, onClose, clickEvents = [], cursorPositions = [
             const timestamp = new Date().toISOString().replace(/[:.]/g, "-").slice(0, 19);
             const finalName = `recording_${timestamp}.mp4`;
 
-            await invoke("move_video_to_videos", {
-                tempPath: videoPath,
-                finalName
+            // Only sent when the file has to be copied to another drive
+            const unlistenMove = await listen<number>("move-progress", (event) => {
+                setExportStatus(`Saving... ${Math.round(event.payload * 100)}%`);
             });
+            try {
+                await invoke("move_video_to_videos", {
+                    tempPath: videoPath,
+                    finalName
+                });
+            } finally {
+                unlistenMove();
+            }
 
             setExportStatus("Saved!");
             setTimeout(() => onClose(), 1000);
@@ -682,6 +814,44 @@ function VideoEditor({ videoPath, onClose, clickEvents = [], cursorPositions = [
         }
     };
 
+    // Save the frame at the playhead as a PNG, rendered like the export
+    const handleExportFrame = async () => {
+        const video = videoRef.current;
+        if (!video) return;
+
+        setIsExporting(true);
+        setExportStatus("Saving frame...");
+
+        try {
+            const timestamp = new Date().toISOString().replace(/[:.]/g, "-").slice(0, 19);
+            const videosDir = await invoke<string>("get_videos_dir_path");
+            const outputPath = `${videosDir}\\recording_${timestamp}_frame.png`;
+            const { width, height } = getExportDimensions(video);
+
+            const { renderStillFrame } = await import('./components/editor/exportRenderer');
+            const frameData = await renderStillFrame(video, currentTime, {
+                width,
+                height,
+                effects,
+                cursorPositions,
+                clickEvents,
+                cursorSettings,
+                canvasSettings,
+            });
+            const frameBase64 = btoa(
+                frameData.reduce((data, byte) => data + String.fromCharCode(byte), '')
+            );
+
+            await invoke("export_frame", { outputPath, width, height, frameBase64 });
+            setExportStatus("Saved!");
+            setTimeout(() => setIsExporting(false), 1000);
+        } catch (error) {
+            console.error("Frame export failed:", error);
+            setExportStatus(`Frame export failed: ${exportErrorMessage(error)}`);
+            setTimeout(() => setIsExporting(false), 2000);
+        }
+    };
+
     const handleSeek = (newTime: number) => {
         if (videoRef.current) {
             videoRef.current.currentTime = newTime;
@@ -740,8 +910,10 @@ function VideoEditor({ videoPath, onClose, clickEvents = [], cursorPositions = [
                 {/* Toolbar */}
                 <Toolbar
                     onAddEffect={addEffect}
+                    onZoomOnClicks={zoomOnClicks}
                     onDeleteEffect={() => selectedEffectId && removeEffect(selectedEffectId)}
                     hasSelection={!!selectedEffectId}
+                    hasClicks={clickEvents.length > 0}
                 />
 
                 {/* Timeline */}
@@ -782,7 +954,9 @@ function VideoEditor({ videoPath, onClose, clickEvents = [], cursorPositions = [
                 onCursorSettingsChange={(updates) => setCursorSettings(prev => ({ ...prev, ...updates }))}
                 onExportSettingsChange={updateExportSettings}
                 onExport={handleExport}
+                onCancelExport={exportCancellable ? handleCancelExport : undefined}
                 onSaveOriginal={handleSaveOriginal}
+                onExportFrame={handleExportFrame}
                 onEffectUpdate={updateEffect}
             />
         </div>
@@ -1,5 +1,6 @@
-import { Effect, EasingPreset } from './types';
-import { EFFECT_CONFIG, ZOOM_EASING_PRESETS } from './constants';
+import { Effect, EasingPreset, EasingCurve } from './types';
+import { EFFECT_CONFIG, ZOOM_EASING_PRESETS, ZOOM_EASING_CURVES, ZOOM_MIN_SCALE } from './constants';
+import { getZoomEaseDuration } from './effectEngine';
 
 interface EffectSettingsProps {
     effect: Effect;
@@ -21,7 +22,7 @@ export function EffectSettings({ effect, onUpdate }: EffectSettingsProps) {
                         <div className="flex items-center gap-2.5">
                             <input
                                 type="range"
-                                min="1.5"
+                                min={ZOOM_MIN_SCALE}
                                 max="4"
                                 step="0.1"
                                 value={effect.scale || 2.5}
@@ -43,11 +44,11 @@ export function EffectSettings({ effect, onUpdate }: EffectSettingsProps) {
                             {(Object.keys(ZOOM_EASING_PRESETS) as EasingPreset[]).map((preset) => (
                                 <button
                                     key={preset}
-                                    className={`px-2 py-1.5 text-xs font-medium rounded-md border transition-all duration-150 ${(effect.easing || 'mellow') === preset
+                                    className={`px-2 py-1.5 text-xs font-medium rounded-md border transition-all duration-150 ${effect.easeDuration === undefined && (effect.easing || 'mellow') === preset
                                             ? 'bg-emerald-50 border-emerald-400 text-emerald-700'
                                             : 'bg-gray-50 border-gray-200 text-gray-600 hover:bg-gray-100'
                                         }`}
-                                    onClick={() => onUpdate(effect.id, { easing: preset })}
+                                    onClick={() => onUpdate(effect.id, { easing: preset, easeDuration: undefined })}
                                 >
                                     {ZOOM_EASING_PRESETS[preset].label}
                                 </button>
@@ -55,6 +56,67 @@ export function EffectSettings({ effect, onUpdate }: EffectSettingsProps) {
                         </div>
                     </div>
 
+                    {/* Explicit easing duration (overrides the preset) */}
+                    <div className="flex flex-col gap-1.5">
+                        <label className="text-xs text-gray-600 font-medium">Animation Duration</label>
+                        <div className="flex items-center gap-2.5">
+                            <input
+                                type="range"
+                                min="0.05"
+                                max={Math.max(0.05, (effect.endTime - effect.startTime) / 2)}
+                                step="0.05"
+                                value={getZoomEaseDuration(effect)}
+                                onChange={(e) => onUpdate(effect.id, {
+                                    easeDuration: parseFloat(e.target.value)
+                                })}
+                                className="range-slider flex-1"
+                            />
+                            <span className="text-xs text-gray-900 font-medium min-w-10 text-right">
+                                {getZoomEaseDuration(effect).toFixed(2)}s
+                            </span>
+                        </div>
+                    </div>
+
+                    {/* Easing Curve */}
+                    <div className="flex flex-col gap-1.5">
+                        <label className="text-xs text-gray-600 font-medium">Animation Curve</label>
+                        <div className="grid grid-cols-3 gap-1">
+                            {(Object.keys(ZOOM_EASING_CURVES) as EasingCurve[]).map((curve) => (
+                                <button
+                                    key={curve}
+                                    className={`px-2 py-1.5 text-xs font-medium rounded-md border transition-all duration-150 ${(effect.curve || 'smooth') === curve
+                                            ? 'bg-emerald-50 border-emerald-400 text-emerald-700'
+                                            : 'bg-gray-50 border-gray-200 text-gray-600 hover:bg-gray-100'
+                                        }`}
+                                    onClick={() => onUpdate(effect.id, { curve })}
+                                >
+                                    {ZOOM_EASING_CURVES[curve].label}
+                                </button>
+                            ))}
+                        </div>
+                    </div>
+
+                    {/* Background dim while zoomed */}
+                    <div className="flex flex-col gap-1.5">
+                        <label className="text-xs text-gray-600 font-medium">Dim Background</label>
+                        <div className="flex items-center gap-2.5">
+                            <input
+                                type="range"
+                                min="0"
+                                max="1"
+                                step="0.05"
+                                value={effect.dimBackground ?? 0}
+                                onChange={(e) => onUpdate(effect.id, {
+                                    dimBackground: parseFloat(e.target.value)
+                                })}
+                                className="range-slider flex-1"
+                            />
+                            <span className="text-xs text-gray-900 font-medium min-w-10 text-right">
+                                {Math.round((effect.dimBackground ?? 0) * 100)}%
+                            </span>
+                        </div>
+                    </div>
+
                     <div className="text-xs text-gray-500">
                         Zoom follows cursor position automatically.
                     </div>
@@ -1,8 +1,9 @@
 import { useState } from 'react';
-import { Effect, CanvasSettings, ExportSettings, SidebarTab, CursorSettings } from './types';
+import { Effect, CanvasSettings, ExportSettings, ExportEncoder, SidebarTab, CursorSettings, BackgroundGradient, AspectRatio } from './types';
 import { formatTimeDetailed } from './utils';
 import { EffectSettings } from './EffectSettings';
-import { RESOLUTION_OPTIONS, FORMAT_OPTIONS, QUALITY_OPTIONS, CURSOR_STYLES } from './constants';
+import { RESOLUTION_OPTIONS, FORMAT_OPTIONS, QUALITY_OPTIONS, ENCODER_OPTIONS, SPEED_OPTIONS, ASPECT_RATIO_OPTIONS, CURSOR_STYLES } from './constants';
+import { loadImage, hasTransparency } from './canvasRenderer';
 
 // Preset background colors
 const BACKGROUND_PRESETS = [
@@ -14,6 +15,18 @@ const BACKGROUND_PRESETS = [
     { name: 'Light Gray', color: '#f0f0f0' },
 ];
 
+// Preset background gradients
+const GRADIENT_PRESETS: { name: string; gradient: BackgroundGradient }[] = [
+    { name: 'Sunset', gradient: { from: '#ff7e5f', to: '#feb47b', angle: 135 } },
+    { name: 'Ocean', gradient: { from: '#2193b0', to: '#6dd5ed', angle: 135 } },
+    { name: 'Violet', gradient: { from: '#4776e6', to: '#8e54e9', angle: 135 } },
+    { name: 'Mint', gradient: { from: '#11998e', to: '#38ef7d', angle: 135 } },
+    { name: 'Night', gradient: { from: '#0f2027', to: '#2c5364', angle: 135 } },
+    { name: 'Peach', gradient: { from: '#ee9ca7', to: '#ffdde1', angle: 135 } },
+];
+
+const cssGradient = (g: BackgroundGradient) => `linear-gradient(${g.angle}deg, ${g.from}, ${g.to})`;
+
 // Tab icons as SVG components
 const TabIcons = {
     background: (
@@ -54,7 +67,9 @@ interface SidebarProps {
     onCursorSettingsChange: (settings: Partial<CursorSettings>) => void;
     onExportSettingsChange: (settings: Partial<ExportSettings>) => void;
     onExport: () => void;
+    onCancelExport?: () => void;  // Set while the running export can be cancelled
     onSaveOriginal: () => void;
+    onExportFrame: () => void;
     onEffectUpdate: (id: string, updates: Partial<Effect>) => void;
 }
 
@@ -71,7 +86,9 @@ export function Sidebar({
     onCursorSettingsChange,
     onExportSettingsChange,
     onExport,
+    onCancelExport,
     onSaveOriginal,
+    onExportFrame,
     onEffectUpdate,
 }: SidebarProps) {
     const [activeTab, setActiveTab] = useState<SidebarTab>('background');
@@ -99,6 +116,14 @@ export function Sidebar({
                     </svg>
                     {isExporting ? exportStatus : "Export video"}
                 </button>
+                {isExporting && onCancelExport && (
+                    <button
+                        className="w-full mt-2 px-6 py-2 bg-white border border-gray-300 rounded-xl text-gray-700 text-xs font-medium cursor-pointer transition-all duration-150 hover:bg-gray-50"
+                        onClick={onCancelExport}
+                    >
+                        Cancel
+                    </button>
+                )}
             </div>
 
             {/* Main content area with tab bar */}
@@ -139,6 +164,7 @@ export function Sidebar({
                             exportSettings={exportSettings}
                             onExportSettingsChange={onExportSettingsChange}
                             onSaveOriginal={onSaveOriginal}
+                            onExportFrame={onExportFrame}
                             isExporting={isExporting}
                             trimStart={trimStart}
                             trimEnd={trimEnd}
@@ -174,12 +200,12 @@ function BackgroundTab({
                     <button
                         key={preset.color}
                         title={preset.name}
-                        className={`w-full aspect-square rounded-md border-2 transition-all duration-200 hover:scale-105 ${canvasSettings.backgroundColor === preset.color
+                        className={`w-full aspect-square rounded-md border-2 transition-all duration-200 hover:scale-105 ${!canvasSettings.backgroundGradient && canvasSettings.backgroundColor === preset.color
                             ? 'border-indigo-500 ring-2 ring-indigo-200'
                             : 'border-gray-200 hover:border-gray-300'
                             }`}
                         style={{ backgroundColor: preset.color }}
-                        onClick={() => onCanvasSettingsChange({ backgroundColor: preset.color })}
+                        onClick={() => onCanvasSettingsChange({ backgroundColor: preset.color, backgroundGradient: null })}
                     />
                 ))}
             </div>
@@ -189,20 +215,171 @@ function BackgroundTab({
                 <input
                     type="color"
                     value={canvasSettings.backgroundColor}
-                    onChange={(e) => onCanvasSettingsChange({ backgroundColor: e.target.value })}
+                    onChange={(e) => onCanvasSettingsChange({ backgroundColor: e.target.value, backgroundGradient: null })}
                     className="w-8 h-8 rounded cursor-pointer border border-gray-200"
                 />
                 <input
                     type="text"
                     value={canvasSettings.backgroundColor}
-                    onChange={(e) => onCanvasSettingsChange({ backgroundColor: e.target.value })}
+                    onChange={(e) => onCanvasSettingsChange({ backgroundColor: e.target.value, backgroundGradient: null })}
                     className="flex-1 px-2 py-1 text-xs font-mono border border-gray-200 rounded"
                     placeholder="#000000"
                 />
             </div>
 
+            {/* Gradient Presets */}
+            <div className="grid grid-cols-6 gap-1.5">
+                {GRADIENT_PRESETS.map((preset) => {
+                    const current = canvasSettings.backgroundGradient;
+                    const selected = current !== null
+                        && current.from === preset.gradient.from
+                        && current.to === preset.gradient.to;
+                    return (
+                        <button
+                            key={preset.name}
+                            title={preset.name}
+                            className={`w-full aspect-square rounded-md border-2 transition-all duration-200 hover:scale-105 ${selected
+                                ? 'border-indigo-500 ring-2 ring-indigo-200'
+                                : 'border-gray-200 hover:border-gray-300'
+                                }`}
+                            style={{ background: cssGradient(preset.gradient) }}
+                            onClick={() => onCanvasSettingsChange({
+                                backgroundGradient: { ...preset.gradient, angle: current?.angle ?? preset.gradient.angle },
+                            })}
+                        />
+                    );
+                })}
+            </div>
+
+            {/* Custom gradient colors and angle */}
+            {canvasSettings.backgroundGradient && (
+                <div className="flex flex-col gap-2">
+                    <div className="flex items-center gap-2">
+                        <input
+                            type="color"
+                            value={canvasSettings.backgroundGradient.from}
+                            onChange={(e) => onCanvasSettingsChange({
+                                backgroundGradient: { ...canvasSettings.backgroundGradient!, from: e.target.value },
+                            })}
+                            className="w-8 h-8 rounded cursor-pointer border border-gray-200"
+                        />
+                        <div
+                            className="flex-1 h-3 rounded"
+                            style={{ background: cssGradient({ ...canvasSettings.backgroundGradient, angle: 90 }) }}
+                        />
+                        <input
+                            type="color"
+                            value={canvasSettings.backgroundGradient.to}
+                            onChange={(e) => onCanvasSettingsChange({
+                                backgroundGradient: { ...canvasSettings.backgroundGradient!, to: e.target.value },
+                            })}
+                            className="w-8 h-8 rounded cursor-pointer border border-gray-200"
+                        />
+                    </div>
+                    <div className="flex items-center gap-2.5">
+                        <input
+                            type="range"
+                            min="0"
+                            max="360"
+                            step="15"
+                            value={canvasSettings.backgroundGradient.angle}
+                            onChange={(e) => onCanvasSettingsChange({
+                                backgroundGradient: { ...canvasSettings.backgroundGradient!, angle: parseInt(e.target.value) },
+                            })}
+                            className="flex-1"
+                        />
+                        <span className="text-xs text-gray-900 font-medium min-w-10 text-right">{canvasSettings.backgroundGradient.angle}°</span>
+                    </div>
+                </div>
+            )}
+
+            {/* Background image (drawn over the color, which stays as a fallback) */}
+            <div className="flex flex-col gap-2">
+                <label className="text-xs text-gray-600 font-medium">Background Image</label>
+                <div className="flex items-center gap-2">
+                    <label className="flex-1 px-2 py-1 text-xs text-center border border-gray-200 rounded cursor-pointer hover:border-gray-300">
+                        {canvasSettings.backgroundImage ? 'Change image' : 'Choose image'}
+                        <input
+                            type="file"
+                            accept="image/*"
+                            className="hidden"
+                            onChange={(e) => {
+                                const file = e.target.files?.[0];
+                                if (!file) return;
+                                if (canvasSettings.backgroundImage) URL.revokeObjectURL(canvasSettings.backgroundImage);
+                                onCanvasSettingsChange({ backgroundImage: URL.createObjectURL(file) });
+                                e.target.value = '';
+                            }}
+                        />
+                    </label>
+                    {canvasSettings.backgroundImage && (
+                        <button
+                            className="px-2 py-1 text-xs border border-gray-200 rounded hover:border-gray-300"
+                            onClick={() => {
+                                URL.revokeObjectURL(canvasSettings.backgroundImage!);
+                                onCanvasSettingsChange({ backgroundImage: null });
+                            }}
+                        >
+                            Remove
+                        </button>
+                    )}
+                </div>
+            </div>
+
+            {/* Blurred video fill (drawn over the color and image) */}
+            <div className="flex flex-col gap-2">
+                <label className="text-xs text-gray-600 font-medium">Background Blur</label>
+                <div className="flex items-center gap-2.5">
+                    <input
+                        type="range"
+                        min="0"
+                        max="80"
+                        step="4"
+                        value={canvasSettings.backgroundBlur}
+                        onChange={(e) => onCanvasSettingsChange({ backgroundBlur: parseInt(e.target.value) })}
+                        className="flex-1"
+                    />
+                    <span className="text-xs text-gray-900 font-medium min-w-10 text-right">
+                        {canvasSettings.backgroundBlur > 0 ? `${canvasSettings.backgroundBlur}px` : 'Off'}
+                    </span>
+                </div>
+            </div>
+
             <div className="h-px bg-gray-200" />
 
+            {/* Aspect Ratio */}
+            <div className="flex flex-col gap-2">
+                <label className="text-xs text-gray-600 font-medium">Aspect Ratio</label>
+                <select
+                    value={canvasSettings.aspectRatio}
+                    onChange={(e) => onCanvasSettingsChange({ aspectRatio: e.target.value as AspectRatio })}
+                    className="w-full px-3 py-2 text-sm border border-gray-200 rounded-lg bg-white focus:outline-none focus:ring-2 focus:ring-indigo-500 focus:border-transparent"
+                >
+                    {ASPECT_RATIO_OPTIONS.map((opt) => (
+                        <option key={opt.value} value={opt.value}>{opt.label}</option>
+                    ))}
+                </select>
+                {canvasSettings.aspectRatio !== 'original' && (
+                    <div className="flex gap-2">
+                        {([
+                            { value: 'letterbox', label: 'Fit' },
+                            { value: 'crop', label: 'Fill' },
+                        ] as const).map((opt) => (
+                            <button
+                                key={opt.value}
+                                onClick={() => onCanvasSettingsChange({ aspectFit: opt.value })}
+                                className={`flex-1 px-3 py-2 text-xs font-medium rounded-lg border transition-all duration-150 ${canvasSettings.aspectFit === opt.value
+                                    ? 'bg-indigo-50 border-indigo-400 text-indigo-700'
+                                    : 'bg-gray-50 border-gray-200 text-gray-600 hover:bg-gray-100'
+                                    }`}
+                            >
+                                {opt.label}
+                            </button>
+                        ))}
+                    </div>
+                )}
+            </div>
+
             {/* Corner Radius */}
             <div className="flex flex-col gap-2">
                 <label className="text-xs text-gray-600 font-medium">Corner Radius</label>
@@ -239,16 +416,71 @@ function BackgroundTab({
 
             <div className="h-px bg-gray-200" />
 
-            {/* Click Ripple Toggle */}
+            {/* Drop Shadow */}
+            <div className="flex flex-col gap-2">
+                <label className="flex items-center gap-2 cursor-pointer">
+                    <input
+                        type="checkbox"
+                        checked={canvasSettings.shadow.enabled}
+                        onChange={(e) => onCanvasSettingsChange({ shadow: { ...canvasSettings.shadow, enabled: e.target.checked } })}
+                        className="w-4 h-4 rounded border-gray-300 text-indigo-600 focus:ring-indigo-500"
+                    />
+                    <span className="text-xs text-gray-600 font-medium">Drop shadow</span>
+                </label>
+                {canvasSettings.shadow.enabled && (
+                    <>
+                        {([
+                            { key: 'blur', label: 'Blur', min: 0, max: 100, step: 2, unit: 'px' },
+                            { key: 'offsetX', label: 'Offset X', min: -50, max: 50, step: 1, unit: 'px' },
+                            { key: 'offsetY', label: 'Offset Y', min: -50, max: 50, step: 1, unit: 'px' },
+                        ] as const).map(({ key, label, min, max, step, unit }) => (
+                            <div key={key} className="flex items-center gap-2.5">
+                                <span className="text-xs text-gray-600 min-w-14">{label}</span>
+                                <input
+                                    type="range"
+                                    min={min}
+                                    max={max}
+                                    step={step}
+                                    value={canvasSettings.shadow[key]}
+                                    onChange={(e) => onCanvasSettingsChange({ shadow: { ...canvasSettings.shadow, [key]: parseInt(e.target.value) } })}
+                                    className="flex-1"
+                                />
+                                <span className="text-xs text-gray-900 font-medium min-w-10 text-right">{canvasSettings.shadow[key]}{unit}</span>
+                            </div>
+                        ))}
+                        <div className="flex items-center gap-2.5">
+                            <span className="text-xs text-gray-600 min-w-14">Opacity</span>
+                            <input
+                                type="range"
+                                min="0"
+                                max="100"
+                                step="5"
+                                value={Math.round(canvasSettings.shadow.opacity * 100)}
+                                onChange={(e) => onCanvasSettingsChange({ shadow: { ...canvasSettings.shadow, opacity: parseInt(e.target.value) / 100 } })}
+                                className="flex-1"
+                            />
+                            <input
+                                type="color"
+                                value={canvasSettings.shadow.color}
+                                onChange={(e) => onCanvasSettingsChange({ shadow: { ...canvasSettings.shadow, color: e.target.value } })}
+                                className="w-6 h-6 rounded cursor-pointer border border-gray-200"
+                            />
+                        </div>
+                    </>
+                )}
+            </div>
+
+            {/* Vignette */}
             <label className="flex items-center gap-2 cursor-pointer">
                 <input
                     type="checkbox"
-                    checked={canvasSettings.clickRippleEnabled}
-                    onChange={(e) => onCanvasSettingsChange({ clickRippleEnabled: e.target.checked })}
+                    checked={canvasSettings.vignette}
+                    onChange={(e) => onCanvasSettingsChange({ vignette: e.target.checked })}
                     className="w-4 h-4 rounded border-gray-300 text-indigo-600 focus:ring-indigo-500"
                 />
-                <span className="text-xs text-gray-600 font-medium">Show click ripples</span>
+                <span className="text-xs text-gray-600 font-medium">Vignette</span>
             </label>
+
         </div>
     );
 }
@@ -261,6 +493,27 @@ function CursorTab({
     cursorSettings: CursorSettings;
     onCursorSettingsChange: (settings: Partial<CursorSettings>) => void;
 }) {
+    const [customImageError, setCustomImageError] = useState('');
+
+    const handleCustomImage = async (file: File) => {
+        const url = URL.createObjectURL(file);
+        const image = await loadImage(url);
+        if (!image) {
+            URL.revokeObjectURL(url);
+            setCustomImageError(`"${file.name}" is not a readable image`);
+            return;
+        }
+        // A fully opaque image would draw as a solid rectangle
+        if (!hasTransparency(image)) {
+            URL.revokeObjectURL(url);
+            setCustomImageError(`"${file.name}" has no transparency; use a PNG with an alpha channel`);
+            return;
+        }
+        setCustomImageError('');
+        if (cursorSettings.customImage) URL.revokeObjectURL(cursorSettings.customImage);
+        onCursorSettingsChange({ style: 'custom', customImage: url });
+    };
+
     return (
         <div className="flex flex-col gap-5">
             <h3 className="text-sm font-semibold m-0 text-gray-900">Cursor</h3>
@@ -295,6 +548,24 @@ function CursorTab({
                                 </button>
                             ))}
                         </div>
+                        <label className={`px-3 py-2 text-xs font-medium text-center rounded-lg border cursor-pointer transition-all duration-150 ${cursorSettings.style === 'custom'
+                            ? 'bg-indigo-50 border-indigo-400 text-indigo-700'
+                            : 'bg-gray-50 border-gray-200 text-gray-600 hover:bg-gray-100'
+                            }`}
+                        >
+                            {cursorSettings.customImage ? 'Custom image (change)' : 'Custom image...'}
+                            <input
+                                type="file"
+                                accept="image/png,image/*"
+                                className="hidden"
+                                onChange={(e) => {
+                                    const file = e.target.files?.[0];
+                                    if (file) handleCustomImage(file);
+                                    e.target.value = '';
+                                }}
+                            />
+                        </label>
+                        {customImageError && <p className="text-xs text-red-500 m-0">{customImageError}</p>}
                     </div>
 
                     {/* Cursor Size */}
@@ -334,6 +605,44 @@ function CursorTab({
                         </div>
                     </div>
 
+                    {/* Opacity */}
+                    <div className="flex flex-col gap-2">
+                        <label className="text-xs text-gray-600 font-medium">Opacity</label>
+                        <div className="flex items-center gap-2.5">
+                            <input
+                                type="range"
+                                min="10"
+                                max="100"
+                                step="5"
+                                value={Math.round(cursorSettings.opacity * 100)}
+                                onChange={(e) => onCursorSettingsChange({ opacity: parseInt(e.target.value) / 100 })}
+                                className="flex-1"
+                            />
+                            <span className="text-xs text-gray-900 font-medium min-w-10 text-right">{Math.round(cursorSettings.opacity * 100)}%</span>
+                        </div>
+                    </div>
+
+                    {/* Outline */}
+                    <div className="flex items-center gap-2">
+                        <label className="flex flex-1 items-center gap-2 cursor-pointer">
+                            <input
+                                type="checkbox"
+                                checked={cursorSettings.outline}
+                                onChange={(e) => onCursorSettingsChange({ outline: e.target.checked })}
+                                className="w-4 h-4 rounded border-gray-300 text-indigo-600 focus:ring-indigo-500"
+                            />
+                            <span className="text-xs text-gray-600 font-medium">Outline</span>
+                        </label>
+                        {cursorSettings.outline && (
+                            <input
+                                type="color"
+                                value={cursorSettings.outlineColor}
+                                onChange={(e) => onCursorSettingsChange({ outlineColor: e.target.value })}
+                                className="w-6 h-6 rounded cursor-pointer border border-gray-200"
+                            />
+                        )}
+                    </div>
+
                     {/* Smoothing */}
                     <div className="flex flex-col gap-2">
                         <label className="text-xs text-gray-600 font-medium">Smoothing</label>
@@ -354,6 +663,25 @@ function CursorTab({
                         <p className="text-xs text-gray-400">Higher = smoother cursor movement</p>
                     </div>
 
+                    {/* Trail */}
+                    <div className="flex flex-col gap-2">
+                        <label className="text-xs text-gray-600 font-medium">Trail</label>
+                        <div className="flex items-center gap-2.5">
+                            <input
+                                type="range"
+                                min="0"
+                                max="300"
+                                step="25"
+                                value={cursorSettings.trailLength}
+                                onChange={(e) => onCursorSettingsChange({ trailLength: parseInt(e.target.value) })}
+                                className="flex-1"
+                            />
+                            <span className="text-xs text-gray-900 font-medium min-w-12 text-right">
+                                {cursorSettings.trailLength === 0 ? 'Off' : `${cursorSettings.trailLength}ms`}
+                            </span>
+                        </div>
+                    </div>
+
                     <div className="h-px bg-gray-200" />
 
                     {/* Effects Toggles */}
@@ -377,6 +705,66 @@ function CursorTab({
                             />
                             <span className="text-xs text-gray-600 font-medium">Click ripple effect</span>
                         </label>
+                        {cursorSettings.clickRipple && (
+                            <div className="flex items-center gap-2.5">
+                                <span className="text-xs text-gray-600 min-w-14">Ripple</span>
+                                <input
+                                    type="range"
+                                    min="20"
+                                    max="120"
+                                    step="5"
+                                    value={cursorSettings.rippleSize}
+                                    onChange={(e) => onCursorSettingsChange({ rippleSize: parseInt(e.target.value) })}
+                                    className="flex-1"
+                                />
+                                <input
+                                    type="color"
+                                    value={cursorSettings.rippleColor}
+                                    onChange={(e) => onCursorSettingsChange({ rippleColor: e.target.value })}
+                                    className="w-6 h-6 rounded cursor-pointer border border-gray-200"
+                                />
+                            </div>
+                        )}
+
+                        <label className="flex items-center gap-2 cursor-pointer">
+                            <input
+                                type="checkbox"
+                                checked={cursorSettings.spotlight.enabled}
+                                onChange={(e) => onCursorSettingsChange({ spotlight: { ...cursorSettings.spotlight, enabled: e.target.checked } })}
+                                className="w-4 h-4 rounded border-gray-300 text-indigo-600 focus:ring-indigo-500"
+                            />
+                            <span className="text-xs text-gray-600 font-medium">Spotlight around cursor</span>
+                        </label>
+                        {cursorSettings.spotlight.enabled && (
+                            <>
+                                <div className="flex items-center gap-2.5">
+                                    <span className="text-xs text-gray-600 min-w-14">Radius</span>
+                                    <input
+                                        type="range"
+                                        min="50"
+                                        max="600"
+                                        step="10"
+                                        value={cursorSettings.spotlight.radius}
+                                        onChange={(e) => onCursorSettingsChange({ spotlight: { ...cursorSettings.spotlight, radius: parseInt(e.target.value) } })}
+                                        className="flex-1"
+                                    />
+                                    <span className="text-xs text-gray-900 font-medium min-w-10 text-right">{cursorSettings.spotlight.radius}px</span>
+                                </div>
+                                <div className="flex items-center gap-2.5">
+                                    <span className="text-xs text-gray-600 min-w-14">Dim</span>
+                                    <input
+                                        type="range"
+                                        min="10"
+                                        max="90"
+                                        step="5"
+                                        value={Math.round(cursorSettings.spotlight.dimOpacity * 100)}
+                                        onChange={(e) => onCursorSettingsChange({ spotlight: { ...cursorSettings.spotlight, dimOpacity: parseInt(e.target.value) / 100 } })}
+                                        className="flex-1"
+                                    />
+                                    <span className="text-xs text-gray-900 font-medium min-w-10 text-right">{Math.round(cursorSettings.spotlight.dimOpacity * 100)}%</span>
+                                </div>
+                            </>
+                        )}
                     </div>
                 </>
             )}
@@ -389,6 +777,7 @@ function ExportTab({
     exportSettings,
     onExportSettingsChange,
     onSaveOriginal,
+    onExportFrame,
     isExporting,
     trimStart,
     trimEnd,
@@ -396,6 +785,7 @@ function ExportTab({
     exportSettings: ExportSettings;
     onExportSettingsChange: (settings: Partial<ExportSettings>) => void;
     onSaveOriginal: () => void;
+    onExportFrame: () => void;
     isExporting: boolean;
     trimStart: number;
     trimEnd: number;
@@ -456,6 +846,95 @@ function ExportTab({
                 </div>
             </div>
 
+            {/* Encoder (WebM is always encoded in software) */}
+            {exportSettings.format === 'mp4' && (
+                <div className="flex flex-col gap-2">
+                    <label className="text-xs text-gray-600 font-medium">Encoder</label>
+                    <select
+                        value={exportSettings.encoder}
+                        onChange={(e) => onExportSettingsChange({ encoder: e.target.value as ExportEncoder })}
+                        className="w-full px-3 py-2 text-sm border border-gray-200 rounded-lg bg-white focus:outline-none focus:ring-2 focus:ring-indigo-500 focus:border-transparent"
+                    >
+                        {ENCODER_OPTIONS.map((opt) => (
+                            <option key={opt.value} value={opt.value}>{opt.label}</option>
+                        ))}
+                    </select>
+                </div>
+            )}
+
+            {/* File size cap (switches from quality to a bitrate fitted to the duration) */}
+            <div className="flex flex-col gap-2">
+                <label className="text-xs text-gray-600 font-medium">Max File Size</label>
+                <div className="flex items-center gap-2">
+                    <input
+                        type="number"
+                        min="1"
+                        step="1"
+                        value={exportSettings.maxFileSizeMb ?? ''}
+                        onChange={(e) => {
+                            const value = parseFloat(e.target.value);
+                            onExportSettingsChange({ maxFileSizeMb: value > 0 ? value : null });
+                        }}
+                        className="flex-1 px-2 py-1 text-xs border border-gray-200 rounded"
+                        placeholder="No limit"
+                    />
+                    <span className="text-xs text-gray-600">MB</span>
+                </div>
+            </div>
+
+            {/* Speed */}
+            <div className="flex flex-col gap-2">
+                <label className="text-xs text-gray-600 font-medium">Speed</label>
+                <div className="flex gap-2">
+                    {SPEED_OPTIONS.map((opt) => (
+                        <button
+                            key={opt.value}
+                            onClick={() => onExportSettingsChange({ speed: opt.value })}
+                            className={`flex-1 px-3 py-2 text-xs font-medium rounded-lg border transition-all duration-150 ${exportSettings.speed === opt.value
+                                ? 'bg-indigo-50 border-indigo-400 text-indigo-700'
+                                : 'bg-gray-50 border-gray-200 text-gray-600 hover:bg-gray-100'
+                                }`}
+                        >
+                            {opt.label}
+                        </button>
+                    ))}
+                </div>
+            </div>
+
+            {/* Fades */}
+            <div className="flex flex-col gap-2">
+                <label className="text-xs text-gray-600 font-medium">Fade</label>
+                {([
+                    { key: 'fadeInSecs', label: 'In' },
+                    { key: 'fadeOutSecs', label: 'Out' },
+                ] as const).map(({ key, label }) => (
+                    <div key={key} className="flex items-center gap-2.5">
+                        <span className="text-xs text-gray-600 min-w-14">{label}</span>
+                        <input
+                            type="range"
+                            min="0"
+                            max="3"
+                            step="0.25"
+                            value={exportSettings[key]}
+                            onChange={(e) => onExportSettingsChange({ [key]: parseFloat(e.target.value) })}
+                            className="flex-1"
+                        />
+                        <span className="text-xs text-gray-900 font-medium min-w-10 text-right">{exportSettings[key]}s</span>
+                    </div>
+                ))}
+            </div>
+
+            {/* Loudness normalization (skipped for recordings without audio) */}
+            <label className="flex items-center gap-2 cursor-pointer">
+                <input
+                    type="checkbox"
+                    checked={exportSettings.normalizeAudio}
+                    onChange={(e) => onExportSettingsChange({ normalizeAudio: e.target.checked })}
+                    className="w-4 h-4 rounded border-gray-300 text-indigo-600 focus:ring-indigo-500"
+                />
+                <span className="text-xs text-gray-600 font-medium">Normalize audio loudness</span>
+            </label>
+
             <div className="h-px bg-gray-200" />
 
             {/* Trim Info */}
@@ -463,7 +942,7 @@ function ExportTab({
                 <label className="text-xs text-gray-600 font-medium">Duration</label>
                 <div className="flex flex-col gap-1 text-xs text-gray-600">
                     <span>{formatTimeDetailed(trimStart)} - {formatTimeDetailed(trimEnd)}</span>
-                    <span className="font-semibold text-indigo-500">{formatTimeDetailed(trimEnd - trimStart)}</span>
+                    <span className="font-semibold text-indigo-500">{formatTimeDetailed((trimEnd - trimStart) / exportSettings.speed)}</span>
                 </div>
             </div>
 
@@ -477,6 +956,15 @@ function ExportTab({
             >
                 Keep Original
             </button>
+
+            {/* Still of the current playhead position */}
+            <button
+                className="px-4 py-2.5 bg-transparent border border-gray-300 rounded-lg text-gray-600 text-sm font-medium cursor-pointer transition-all duration-200 hover:bg-gray-50 hover:border-gray-400 disabled:opacity-50 disabled:cursor-not-allowed"
+                onClick={onExportFrame}
+                disabled={isExporting}
+            >
+                Save Current Frame
+            </button>
         </div>
     );
 }
@@ -2,11 +2,13 @@ import { EffectType } from './types';
 
 interface ToolbarProps {
     onAddEffect: (type: EffectType) => void;
+    onZoomOnClicks: () => void;
     onDeleteEffect: () => void;
     hasSelection: boolean;
+    hasClicks: boolean;
 }
 
-export function Toolbar({ onAddEffect, onDeleteEffect, hasSelection }: ToolbarProps) {
+export function Toolbar({ onAddEffect, onZoomOnClicks, onDeleteEffect, hasSelection, hasClicks }: ToolbarProps) {
     return (
         <div className="flex items-center gap-2 py-3 shrink-0">
             <button
@@ -42,6 +44,17 @@ export function Toolbar({ onAddEffect, onDeleteEffect, hasSelection }: ToolbarPr
                     <polyline points="12,6 12,12 16,14" />
                 </svg>
             </button>
+            <button
+                className="w-10 h-10 border border-gray-300 bg-white rounded-lg cursor-pointer flex items-center justify-center text-gray-600 transition-all duration-200 hover:bg-gray-100 hover:border-gray-400 hover:text-gray-900 disabled:opacity-40 disabled:cursor-not-allowed"
+                onClick={onZoomOnClicks}
+                disabled={!hasClicks}
+                title="Zoom on Every Click (replaces zoom effects)"
+            >
+                <svg viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2" className="w-4.5 h-4.5">
+                    <path d="M4,4 L10,19 L12,12 L19,10 Z" />
+                    <circle cx="17" cy="17" r="4" />
+                </svg>
+            </button>
             <div className="w-px h-6 bg-gray-300 mx-1" />
             {hasSelection && (
                 <button
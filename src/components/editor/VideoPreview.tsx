@@ -1,5 +1,6 @@
-import { RefObject, useRef, useState, useEffect, useCallback } from 'react';
+import { RefObject, useRef, useEffect, useCallback } from 'react';
 import { Effect, CursorPosition, ClickEvent, CanvasSettings, CursorSettings } from './types';
+import { aspectRatioValue } from './utils';
 import {
     renderFrame,
     createViewportState,
@@ -26,14 +27,6 @@ interface VideoPreviewProps {
     formatTimeDetailed: (seconds: number) => string;
 }
 
-// Click ripple component - renders expanding circle at click position
-interface RippleState {
-    id: string;
-    x: number; // Normalized 0-1
-    y: number; // Normalized 0-1
-    startTime: number;
-}
-
 export function VideoPreview({
     videoUrl,
     videoRef,
@@ -61,13 +54,10 @@ export function VideoPreview({
     // Animation loop ref
     const rafIdRef = useRef<number | null>(null);
 
-    // Active ripples for click animation
-    const [activeRipples, setActiveRipples] = useState<RippleState[]>([]);
-    const lastProcessedClickRef = useRef<number>(-1);
-
     // Store props in refs to avoid closure stale values in RAF loop
     const activeEffectsRef = useRef(activeEffects);
     const cursorPositionsRef = useRef(cursorPositions);
+    const clickEventsRef = useRef(clickEvents);
     const cursorSettingsRef = useRef(cursorSettings);
     const canvasSettingsRef = useRef(canvasSettings);
 
@@ -75,9 +65,10 @@ export function VideoPreview({
     useEffect(() => {
         activeEffectsRef.current = activeEffects;
         cursorPositionsRef.current = cursorPositions;
+        clickEventsRef.current = clickEvents;
         cursorSettingsRef.current = cursorSettings;
         canvasSettingsRef.current = canvasSettings;
-    }, [activeEffects, cursorPositions, cursorSettings, canvasSettings]);
+    }, [activeEffects, cursorPositions, clickEvents, cursorSettings, canvasSettings]);
 
     // Initialize canvas when video loads or canvas size changes
     const initCanvas = useCallback(() => {
@@ -85,20 +76,20 @@ export function VideoPreview({
         const video = videoRef.current;
         if (!canvas || !video || !videoLoaded) return;
 
-        // Set canvas size to match video aspect ratio within container
+        // Set canvas size to match the output aspect ratio within container
         const container = canvas.parentElement;
         if (!container) return;
 
         const containerRect = container.getBoundingClientRect();
-        const aspectRatio = video.videoWidth / video.videoHeight;
+        const aspectRatio = aspectRatioValue(canvasSettings.aspectRatio, video.videoWidth, video.videoHeight);
 
         let canvasWidth: number, canvasHeight: number;
         if (containerRect.width / containerRect.height > aspectRatio) {
-            // Container is wider than video
+            // Container is wider than the output frame
             canvasHeight = containerRect.height;
             canvasWidth = canvasHeight * aspectRatio;
         } else {
-            // Container is taller than video
+            // Container is taller than the output frame
             canvasWidth = containerRect.width;
             canvasHeight = canvasWidth / aspectRatio;
         }
@@ -125,7 +116,7 @@ export function VideoPreview({
         } catch (e) {
             console.error('Failed to create canvas context:', e);
         }
-    }, [videoRef, videoLoaded]);
+    }, [videoRef, videoLoaded, canvasSettings.aspectRatio]);
 
     // Initialize canvas when video loads
     useEffect(() => {
@@ -139,43 +130,6 @@ export function VideoPreview({
         return () => window.removeEventListener('resize', handleResize);
     }, [initCanvas]);
 
-    // Process click events to show ripples during playback
-    useEffect(() => {
-        if (!canvasSettings.clickRippleEnabled || !isPlaying) return;
-
-        const currentTimeMs = currentTime * 1000;
-        const RIPPLE_DURATION_MS = 400;
-        const CLICK_TOLERANCE_MS = 50;
-
-        clickEvents.forEach((click, index) => {
-            if (index <= lastProcessedClickRef.current) return;
-
-            const timeDiff = currentTimeMs - click.timestamp_ms;
-            if (timeDiff >= 0 && timeDiff < CLICK_TOLERANCE_MS) {
-                const ripple: RippleState = {
-                    id: `ripple-${click.timestamp_ms}-${index}`,
-                    x: click.x,
-                    y: click.y,
-                    startTime: Date.now(),
-                };
-                setActiveRipples(prev => [...prev, ripple]);
-                lastProcessedClickRef.current = index;
-
-                setTimeout(() => {
-                    setActiveRipples(prev => prev.filter(r => r.id !== ripple.id));
-                }, RIPPLE_DURATION_MS);
-            }
-        });
-    }, [currentTime, clickEvents, isPlaying, canvasSettings.clickRippleEnabled]);
-
-    // Reset click processing when seeking or stopping
-    useEffect(() => {
-        if (!isPlaying) {
-            lastProcessedClickRef.current = -1;
-            setActiveRipples([]);
-        }
-    }, [isPlaying]);
-
     // =========================================================================
     // MAIN ANIMATION LOOP - Uses unified canvas renderer
     // =========================================================================
@@ -201,42 +155,17 @@ export function VideoPreview({
 
             // Render frame using unified renderer
             // This is the SAME code path used for export!
+            // Click ripples are drawn by the renderer too, from video time
             renderFrame(video, time, renderCtx, {
                 effects,
                 cursorPositions: positions,
+                clickEvents: clickEventsRef.current,
                 cursorSettings,
                 canvasSettings,
                 viewportState: viewportStateRef.current,
                 cursorState: cursorStateRef.current,
             });
 
-            // Draw click ripples on top (preview-only feature)
-            if (canvasSettings.clickRippleEnabled && activeRipples.length > 0) {
-                const ctx = renderCtx.ctx;
-                const now = Date.now();
-
-                activeRipples.forEach(ripple => {
-                    const elapsed = now - ripple.startTime;
-                    const progress = Math.min(elapsed / 400, 1);
-                    const radius = 20 + 30 * progress;
-                    const opacity = 1 - progress;
-
-                    ctx.save();
-                    ctx.strokeStyle = `rgba(255, 255, 255, ${opacity * 0.8})`;
-                    ctx.lineWidth = 2;
-                    ctx.beginPath();
-                    ctx.arc(
-                        ripple.x * renderCtx.width,
-                        ripple.y * renderCtx.height,
-                        radius,
-                        0,
-                        Math.PI * 2
-                    );
-                    ctx.stroke();
-                    ctx.restore();
-                });
-            }
-
             rafIdRef.current = requestAnimationFrame(animate);
         };
 
@@ -247,7 +176,7 @@ export function VideoPreview({
                 cancelAnimationFrame(rafIdRef.current);
             }
         };
-    }, [videoRef, activeRipples]);
+    }, [videoRef]);
 
     return (
         <div className="relative flex-1 min-h-0 bg-gray-900 rounded-xl overflow-hidden flex flex-col">
@@ -263,7 +192,12 @@ export function VideoPreview({
             {/* Canvas container - renders video with all effects */}
             <div
                 className="flex-1 flex items-center justify-center overflow-hidden"
-                style={{ backgroundColor: canvasSettings.backgroundColor }}
+                style={{
+                    backgroundColor: canvasSettings.backgroundColor,
+                    backgroundImage: canvasSettings.backgroundGradient
+                        ? `linear-gradient(${canvasSettings.backgroundGradient.angle}deg, ${canvasSettings.backgroundGradient.from}, ${canvasSettings.backgroundGradient.to})`
+                        : undefined,
+                }}
             >
                 {/* Hidden video element - source for canvas rendering */}
                 <video
@@ -6,8 +6,11 @@
  * 
  * Key capabilities:
  * - Render a video frame with all effects applied
- * - Draw custom cursor overlay
- * - Support for background color and padding
+ * - Rounded corners and drop shadow on the video
+ * - Click highlight ripples
+ * - Draw custom cursor overlay (with optional motion trail)
+ * - Support for background color/gradient/image, blurred video fill and padding
+ * - Optional vignette over the finished frame
  * - Works with both HTMLCanvasElement and OffscreenCanvas
  */
 
@@ -17,10 +20,11 @@ import {
     CursorState,
     computeFrameState,
     computeCanvasTransform,
+    getCursorAtTime,
     createViewportState,
     createCursorState,
 } from './effectEngine';
-import { Effect, CursorPosition, CursorSettings, CanvasSettings, CursorStyle } from './types';
+import { Effect, CursorPosition, ClickEvent, CursorSettings, CanvasSettings, CursorStyle, ShadowSettings, SpotlightSettings } from './types';
 
 // ============================================================================
 // TYPES
@@ -36,12 +40,63 @@ export interface RenderContext {
 export interface RenderOptions {
     effects: Effect[];
     cursorPositions: CursorPosition[];
+    clickEvents: ClickEvent[];
     cursorSettings: CursorSettings;
     canvasSettings: CanvasSettings;
     viewportState: ViewportState;
     cursorState: CursorState;
 }
 
+// ============================================================================
+// IMAGE LOADING
+// ============================================================================
+
+// Loaded images (background, custom cursor), keyed by URL (shared by preview and export)
+const imageCache = new Map<string, HTMLImageElement>();
+
+/**
+ * Load an image, resolving once it can be drawn
+ * 
+ * Resolves with null if the image fails to load or decode, in which case
+ * callers fall back to their non-image rendering.
+ */
+export function loadImage(url: string): Promise<HTMLImageElement | null> {
+    const image = imageCache.get(url) ?? new Image();
+    if (!imageCache.has(url)) {
+        image.src = url;
+        imageCache.set(url, image);
+    }
+    return image.decode().then(() => image, () => null);
+}
+
+/**
+ * Check whether an image has any transparent pixels (i.e. a usable alpha channel)
+ */
+export function hasTransparency(image: HTMLImageElement): boolean {
+    const canvas = new OffscreenCanvas(image.naturalWidth, image.naturalHeight);
+    const ctx = canvas.getContext('2d');
+    if (!ctx) return false;
+    ctx.drawImage(image, 0, 0);
+    const pixels = ctx.getImageData(0, 0, image.naturalWidth, image.naturalHeight).data;
+    for (let i = 3; i < pixels.length; i += 4) {
+        if (pixels[i] < 255) return true;
+    }
+    return false;
+}
+
+/**
+ * Get an image if it's ready to draw; otherwise start loading it and return null
+ * (the preview picks it up on a later frame, export preloads with loadImage)
+ */
+function getLoadedImage(url: string): HTMLImageElement | null {
+    const image = imageCache.get(url);
+    if (!image) {
+        loadImage(url);
+        return null;
+    }
+    return image.complete && image.naturalWidth > 0 ? image : null;
+}
+
 // ============================================================================
 // CURSOR RENDERING
 // ============================================================================
@@ -56,15 +111,38 @@ function drawCursor(
     size: number,
     color: string,
     style: CursorStyle,
-    scale: number = 1.0
+    scale: number = 1.0,
+    opacity: number = 1.0,
+    customImage: string | null = null,
+    outlineColor: string | null = 'black'
 ): void {
     ctx.save();
     ctx.translate(x, y);
     ctx.scale(scale, scale);
+    ctx.globalAlpha = opacity;
 
     const halfSize = size / 2;
 
-    switch (style) {
+    // A custom image that can't be drawn (missing, unreadable, still loading)
+    // falls back to the pointer so the cursor never disappears
+    const image = style === 'custom' && customImage ? getLoadedImage(customImage) : null;
+    const shape: CursorStyle = style === 'custom' && !image ? 'pointer' : style;
+
+    switch (shape) {
+        case 'custom': {
+            // User-supplied image: longest side matches the cursor size,
+            // top-left corner (the usual hotspot) at the cursor position
+            if (image) {
+                // Cursor PNGs are usually much larger than the drawn size;
+                // the default low-quality smoothing makes the edges jagged
+                ctx.imageSmoothingEnabled = true;
+                ctx.imageSmoothingQuality = 'high';
+                const fit = size / Math.max(image.naturalWidth, image.naturalHeight);
+                ctx.drawImage(image, 0, 0, image.naturalWidth * fit, image.naturalHeight * fit);
+            }
+            break;
+        }
+
         case 'pointer':
             // Arrow pointer cursor
             ctx.beginPath();
@@ -79,9 +157,11 @@ function drawCursor(
 
             ctx.fillStyle = color;
             ctx.fill();
-            ctx.strokeStyle = 'black';
-            ctx.lineWidth = 1;
-            ctx.stroke();
+            if (outlineColor) {
+                ctx.strokeStyle = outlineColor;
+                ctx.lineWidth = 1;
+                ctx.stroke();
+            }
             break;
 
         case 'circle':
@@ -89,20 +169,22 @@ function drawCursor(
             ctx.beginPath();
             ctx.arc(0, 0, halfSize * 0.8, 0, Math.PI * 2);
             ctx.fillStyle = color;
-            ctx.globalAlpha = 0.9;
+            ctx.globalAlpha = opacity * 0.9;
             ctx.fill();
-            ctx.globalAlpha = 1;
-            ctx.strokeStyle = 'black';
-            ctx.lineWidth = 0.5;
-            ctx.stroke();
+            ctx.globalAlpha = opacity;
+            if (outlineColor) {
+                ctx.strokeStyle = outlineColor;
+                ctx.lineWidth = 0.5;
+                ctx.stroke();
+            }
 
             // Center dot
             ctx.beginPath();
             ctx.arc(0, 0, halfSize * 0.25, 0, Math.PI * 2);
             ctx.fillStyle = 'black';
-            ctx.globalAlpha = 0.5;
+            ctx.globalAlpha = opacity * 0.5;
             ctx.fill();
-            ctx.globalAlpha = 1;
+            ctx.globalAlpha = opacity;
             break;
 
         case 'crosshair':
@@ -133,6 +215,210 @@ function drawCursor(
     ctx.restore();
 }
 
+// Trail ghosts are at least one 60fps frame apart, and at most this many are drawn
+const TRAIL_MIN_STEP_MS = 1000 / 60;
+const TRAIL_MAX_GHOSTS = 12;
+
+/**
+ * Draw fading copies of the cursor at its positions over the last trailLength ms
+ * 
+ * Positions come straight from the recorded timeline (interpolated), so the
+ * trail traces the real path; opacity ramps down with age.
+ */
+function drawCursorTrail(
+    ctx: CanvasRenderingContext2D | OffscreenCanvasRenderingContext2D,
+    timeMs: number,
+    cursorPositions: CursorPosition[],
+    cursorSettings: CursorSettings,
+    videoRect: { x: number; y: number; width: number; height: number },
+    size: number
+): void {
+    const trailLength = cursorSettings.trailLength;
+    const step = Math.max(TRAIL_MIN_STEP_MS, trailLength / TRAIL_MAX_GHOSTS);
+
+    // Oldest first, so newer ghosts draw on top
+    for (let age = Math.floor(trailLength / step) * step; age >= step; age -= step) {
+        const pos = getCursorAtTime(cursorPositions, timeMs - age);
+        if (!pos) continue;
+
+        drawCursor(
+            ctx,
+            videoRect.x + pos.x * videoRect.width,
+            videoRect.y + pos.y * videoRect.height,
+            size,
+            cursorSettings.color,
+            cursorSettings.style,
+            1.0,
+            0.5 * (1 - age / trailLength) * cursorSettings.opacity,
+            cursorSettings.customImage,
+            cursorSettings.outline ? cursorSettings.outlineColor : null
+        );
+    }
+}
+
+// ============================================================================
+// CLICK HIGHLIGHT RENDERING
+// ============================================================================
+
+const RIPPLE_DURATION_MS = 400;
+
+/**
+ * Helper: Parse a "#rrggbb" (or "rrggbb") color into its channels
+ * 
+ * Malformed colors would give NaN channels, an invalid rgba() that the
+ * canvas silently ignores, so they use the fallback instead.
+ */
+function parseHexColor(
+    color: string,
+    fallback: { r: number; g: number; b: number }
+): { r: number; g: number; b: number } {
+    const hex = color.startsWith('#') ? color.slice(1) : color;
+    if (!/^[0-9a-fA-F]{6}$/.test(hex)) {
+        console.warn(`[CanvasRenderer] Invalid color "${color}", using fallback`);
+        return fallback;
+    }
+    return {
+        r: parseInt(hex.slice(0, 2), 16),
+        g: parseInt(hex.slice(2, 4), 16),
+        b: parseInt(hex.slice(4, 6), 16),
+    };
+}
+
+/**
+ * Draw an expanding, fading ring for every click in the last 400ms
+ * 
+ * Driven by video time (not wall clock) so export renders the same ripples
+ * the preview shows. Positions map through the drawn video rect, so ripples
+ * stay on the clicked spot while zoomed.
+ */
+function drawClickRipples(
+    ctx: CanvasRenderingContext2D | OffscreenCanvasRenderingContext2D,
+    timeMs: number,
+    clickEvents: ClickEvent[],
+    color: string,
+    size: number,
+    videoRect: { x: number; y: number; width: number; height: number },
+    sourceScale: number
+): void {
+    const { r, g, b } = parseHexColor(color, { r: 255, g: 255, b: 255 });
+
+    for (const click of clickEvents) {
+        const elapsed = timeMs - click.timestamp_ms;
+        if (elapsed < 0 || elapsed >= RIPPLE_DURATION_MS) continue;
+
+        const progress = elapsed / RIPPLE_DURATION_MS;
+        const radius = size * (0.4 + 0.6 * progress) * sourceScale;
+        const opacity = 1 - progress;
+
+        ctx.save();
+        ctx.strokeStyle = `rgba(${r}, ${g}, ${b}, ${opacity * 0.8})`;
+        ctx.lineWidth = Math.max(1, 4 * sourceScale);
+        ctx.beginPath();
+        ctx.arc(
+            videoRect.x + click.x * videoRect.width,
+            videoRect.y + click.y * videoRect.height,
+            radius,
+            0,
+            Math.PI * 2
+        );
+        ctx.stroke();
+        ctx.restore();
+    }
+}
+
+// ============================================================================
+// BACKGROUND RENDERING
+// ============================================================================
+
+/**
+ * Fill the canvas with the background image (scaled to cover, centered),
+ * or the gradient/color if there is no image or it isn't loaded yet
+ */
+function drawBackground(
+    ctx: CanvasRenderingContext2D | OffscreenCanvasRenderingContext2D,
+    width: number,
+    height: number,
+    canvasSettings: CanvasSettings
+): void {
+    const gradient = canvasSettings.backgroundGradient;
+    if (gradient) {
+        // Gradient line through the center, long enough that the end colors
+        // land exactly on the corners (same geometry as CSS linear-gradient)
+        const rad = (gradient.angle * Math.PI) / 180;
+        const dx = Math.sin(rad);
+        const dy = -Math.cos(rad);
+        const halfLength = (Math.abs(width * dx) + Math.abs(height * dy)) / 2;
+        const fill = ctx.createLinearGradient(
+            width / 2 - dx * halfLength,
+            height / 2 - dy * halfLength,
+            width / 2 + dx * halfLength,
+            height / 2 + dy * halfLength
+        );
+        fill.addColorStop(0, gradient.from);
+        fill.addColorStop(1, gradient.to);
+        ctx.fillStyle = fill;
+    } else {
+        ctx.fillStyle = canvasSettings.backgroundColor;
+    }
+    ctx.fillRect(0, 0, width, height);
+
+    if (!canvasSettings.backgroundImage) return;
+
+    const image = getLoadedImage(canvasSettings.backgroundImage);
+    if (!image) return;
+
+    // Cover: scale to fill the canvas and crop the overflow evenly
+    const scale = Math.max(width / image.naturalWidth, height / image.naturalHeight);
+    const drawWidth = image.naturalWidth * scale;
+    const drawHeight = image.naturalHeight * scale;
+    ctx.save();
+    ctx.imageSmoothingQuality = 'high';
+    ctx.drawImage(image, (width - drawWidth) / 2, (height - drawHeight) / 2, drawWidth, drawHeight);
+    ctx.restore();
+}
+
+/**
+ * Blend the background over a rendered frame, for fading in and out of it
+ * 
+ * @param amount - 0 leaves the frame as is, 1 shows only the background
+ */
+export function drawFade(
+    renderCtx: RenderContext,
+    canvasSettings: CanvasSettings,
+    amount: number
+): void {
+    if (amount <= 0) return;
+
+    const { ctx, width, height } = renderCtx;
+    ctx.save();
+    ctx.globalAlpha = Math.min(amount, 1);
+    drawBackground(ctx, width, height, canvasSettings);
+    ctx.restore();
+}
+
+// ============================================================================
+// ASPECT RATIO
+// ============================================================================
+
+const clamp01 = (value: number): number => Math.min(Math.max(value, 0), 1);
+
+/**
+ * Centered slice of a video (normalized 0-1) with the target aspect ratio
+ */
+function cropToAspect(
+    videoAspect: number,
+    targetAspect: number
+): { x: number; y: number; width: number; height: number } {
+    if (videoAspect > targetAspect) {
+        // Video is wider: keep full height, trim the sides
+        const width = targetAspect / videoAspect;
+        return { x: (1 - width) / 2, y: 0, width, height: 1 };
+    }
+    // Video is taller: keep full width, trim top and bottom
+    const height = videoAspect / targetAspect;
+    return { x: 0, y: (1 - height) / 2, width: 1, height };
+}
+
 // ============================================================================
 // MAIN RENDER FUNCTION
 // ============================================================================
@@ -141,11 +427,12 @@ function drawCursor(
  * Render a single frame to canvas
  * 
  * FIRST PRINCIPLES:
- * 1. Clear canvas with background color
+ * 1. Clear canvas with background color, gradient or image
  * 2. Compute frame state using effect engine
- * 3. Draw video frame with transforms applied
- * 4. Draw cursor overlay if visible
- * 5. Apply any post-processing (blur, etc.)
+ * 3. Draw video frame with transforms applied (and cursor spotlight)
+ * 4. Draw click ripples if enabled
+ * 5. Draw cursor overlay if visible
+ * 6. Apply any post-processing (blur, etc.)
  * 
  * @param video - Source video element (for preview) or ImageBitmap (for export)
  * @param time - Current time in seconds
@@ -160,7 +447,7 @@ export function renderFrame(
     options: RenderOptions
 ): FrameState {
     const { ctx, width, height } = renderCtx;
-    const { effects, cursorPositions, cursorSettings, canvasSettings, viewportState, cursorState } = options;
+    const { effects, cursorPositions, clickEvents, cursorSettings, canvasSettings, viewportState, cursorState } = options;
 
     // Step 1: Compute frame state
     const frameState = computeFrameState(
@@ -172,16 +459,39 @@ export function renderFrame(
         cursorState
     );
 
-    // Step 2: Clear with background color
-    ctx.fillStyle = canvasSettings.backgroundColor;
-    ctx.fillRect(0, 0, width, height);
+    // Get video dimensions
+    const videoWidth = video instanceof HTMLVideoElement ? video.videoWidth : video.width;
+    const videoHeight = video instanceof HTMLVideoElement ? video.videoHeight : video.height;
+
+    // Step 2: Clear with background color/gradient/image (or the blurred
+    // video), darkened while a zoom with dimming is active
+    drawBackground(ctx, width, height, canvasSettings);
+    if (canvasSettings.backgroundBlur > 0) {
+        drawBlurredFill(ctx, video, videoWidth, videoHeight, width, height, canvasSettings.backgroundBlur);
+    }
+    if (frameState.backgroundDim > 0) {
+        ctx.fillStyle = `rgba(0, 0, 0, ${frameState.backgroundDim})`;
+        ctx.fillRect(0, 0, width, height);
+    }
 
-    // Step 3: Compute transform
+    // Part of the video that is shown (normalized). Letterboxing shows all of
+    // it; crop-to-fill keeps a centered slice with the canvas aspect ratio
+    const crop = canvasSettings.aspectFit === 'crop'
+        ? cropToAspect(videoWidth / videoHeight, width / height)
+        : { x: 0, y: 0, width: 1, height: 1 };
+
+    // Step 3: Compute transform. Zoom targets are in full-video coordinates,
+    // so map them into the shown slice first
     const transform = computeCanvasTransform(
-        frameState,
+        {
+            ...frameState,
+            viewportX: clamp01((frameState.viewportX - crop.x) / crop.width),
+            viewportY: clamp01((frameState.viewportY - crop.y) / crop.height),
+        },
         width,
         height,
-        canvasSettings.paddingPercent
+        canvasSettings.paddingPercent,
+        (videoWidth * crop.width) / (videoHeight * crop.height)
     );
 
     // Step 4: Draw video with transforms
@@ -192,47 +502,93 @@ export function renderFrame(
         ctx.filter = `blur(${frameState.blurIntensity}px)`;
     }
 
-    // Get video dimensions
-    const videoWidth = video instanceof HTMLVideoElement ? video.videoWidth : video.width;
-    const videoHeight = video instanceof HTMLVideoElement ? video.videoHeight : video.height;
+    // Fitted (aspect ratio preserving) and transformed video rect
+    const drawWidth = transform.videoWidth;
+    const drawHeight = transform.videoHeight;
+    const drawX = transform.videoX;
+    const drawY = transform.videoY;
+
+    // Where the whole video would be drawn (it extends past the canvas when
+    // cropped). Normalized cursor and click positions map through this rect
+    const contentRect = {
+        x: drawX - (crop.x / crop.width) * drawWidth,
+        y: drawY - (crop.y / crop.height) * drawHeight,
+        width: drawWidth / crop.width,
+        height: drawHeight / crop.height,
+    };
 
-    // Calculate aspect ratio preserving dimensions
-    const aspectRatio = videoWidth / videoHeight;
-    const canvasAspect = width / height;
+    // Source video pixels -> canvas pixels, so styling follows padding/zoom/output size
+    const sourceScale = contentRect.width / videoWidth;
 
-    let drawWidth: number, drawHeight: number;
-    if (aspectRatio > canvasAspect) {
-        // Video is wider than canvas
-        drawWidth = transform.videoWidth;
-        drawHeight = transform.videoWidth / aspectRatio;
-    } else {
-        // Video is taller than canvas
-        drawHeight = transform.videoHeight;
-        drawWidth = transform.videoHeight * aspectRatio;
+    // Drop shadow: an opaque rounded rect under the video casts it, so it
+    // follows the same pan/zoom and corner radius as the video
+    if (canvasSettings.shadow.enabled) {
+        drawShadow(ctx, drawX, drawY, drawWidth, drawHeight,
+            canvasSettings.borderRadius * sourceScale, canvasSettings.shadow, sourceScale);
     }
 
-    // Center the video in the transformed area
-    const drawX = (width - drawWidth) / 2 + transform.translateX;
-    const drawY = (height - drawHeight) / 2 + transform.translateY;
-
-    // Apply border radius via clip path if needed
+    // Apply border radius via clip path if needed. The radius is in source
+    // video pixels, scaled like the video itself (padding, zoom and canvas
+    // size), so the small preview canvas and a 4K export show the same corners
     if (canvasSettings.borderRadius > 0) {
+        const radius = canvasSettings.borderRadius * sourceScale;
         ctx.beginPath();
-        roundRect(ctx, drawX, drawY, drawWidth, drawHeight, canvasSettings.borderRadius * frameState.scale);
+        roundRect(ctx, drawX, drawY, drawWidth, drawHeight, radius);
         ctx.clip();
     }
 
-    // Draw the video frame
-    ctx.drawImage(video, drawX, drawY, drawWidth, drawHeight);
+    // Draw the video frame (only the shown slice when cropping)
+    ctx.drawImage(
+        video,
+        crop.x * videoWidth, crop.y * videoHeight, crop.width * videoWidth, crop.height * videoHeight,
+        drawX, drawY, drawWidth, drawHeight
+    );
+
+    // Spotlight is drawn on the video (inside the corner clip), so it pans
+    // and zooms with the content
+    if (cursorSettings.spotlight.enabled && frameState.cursorVisible) {
+        drawSpotlight(
+            ctx,
+            contentRect.x + frameState.cursorX * contentRect.width,
+            contentRect.y + frameState.cursorY * contentRect.height,
+            { x: drawX, y: drawY, width: drawWidth, height: drawHeight },
+            cursorSettings.spotlight,
+            sourceScale
+        );
+    }
 
     ctx.restore();
 
-    // Step 5: Draw cursor overlay
+    // Step 5: Draw click ripples (under the cursor)
+    if (cursorSettings.visible && cursorSettings.clickRipple && clickEvents.length > 0) {
+        drawClickRipples(
+            ctx,
+            time * 1000,
+            clickEvents,
+            cursorSettings.rippleColor,
+            cursorSettings.rippleSize,
+            contentRect,
+            sourceScale
+        );
+    }
+
+    // Step 6: Draw cursor overlay
     if (frameState.cursorVisible) {
+        if (cursorSettings.trailLength > 0) {
+            drawCursorTrail(
+                ctx,
+                time * 1000,
+                cursorPositions,
+                cursorSettings,
+                contentRect,
+                cursorSettings.size * frameState.scale
+            );
+        }
+
         // Convert normalized cursor position to canvas coordinates
         // Cursor pos is relative to video content, so we need to map to canvas
-        const cursorCanvasX = drawX + frameState.cursorX * drawWidth;
-        const cursorCanvasY = drawY + frameState.cursorY * drawHeight;
+        const cursorCanvasX = contentRect.x + frameState.cursorX * contentRect.width;
+        const cursorCanvasY = contentRect.y + frameState.cursorY * contentRect.height;
 
         drawCursor(
             ctx,
@@ -241,13 +597,131 @@ export function renderFrame(
             cursorSettings.size * frameState.scale,  // Scale cursor with zoom
             cursorSettings.color,
             cursorSettings.style,
-            frameState.cursorScale
+            frameState.cursorScale,
+            cursorSettings.opacity,
+            cursorSettings.customImage,
+            cursorSettings.outline ? cursorSettings.outlineColor : null
         );
     }
 
+    // Step 7: Vignette over everything, like a lens would
+    if (canvasSettings.vignette) {
+        drawVignette(ctx, width, height);
+    }
+
     return frameState;
 }
 
+/**
+ * Helper: Fill the canvas with the video itself (cover fit), heavily blurred
+ * 
+ * The fill is drawn past the canvas edges by the blur radius, since blur
+ * fades the edges of what it draws into whatever is underneath.
+ */
+function drawBlurredFill(
+    ctx: CanvasRenderingContext2D | OffscreenCanvasRenderingContext2D,
+    video: HTMLVideoElement | ImageBitmap,
+    videoWidth: number,
+    videoHeight: number,
+    width: number,
+    height: number,
+    blur: number
+): void {
+    if (videoWidth === 0 || videoHeight === 0) return;
+
+    const radius = blur * (height / 1080);  // Same look at any output size
+    const scale = Math.max((width + radius * 4) / videoWidth, (height + radius * 4) / videoHeight);
+    const drawWidth = videoWidth * scale;
+    const drawHeight = videoHeight * scale;
+
+    ctx.save();
+    ctx.filter = `blur(${radius}px)`;
+    ctx.drawImage(video, (width - drawWidth) / 2, (height - drawHeight) / 2, drawWidth, drawHeight);
+    ctx.restore();
+}
+
+/**
+ * Helper: Darken the corners of the canvas, clear in the middle
+ */
+function drawVignette(
+    ctx: CanvasRenderingContext2D | OffscreenCanvasRenderingContext2D,
+    width: number,
+    height: number
+): void {
+    const cx = width / 2;
+    const cy = height / 2;
+    const outer = Math.hypot(cx, cy);
+
+    const mask = ctx.createRadialGradient(cx, cy, outer * 0.5, cx, cy, outer);
+    mask.addColorStop(0, 'rgba(0, 0, 0, 0)');
+    mask.addColorStop(1, 'rgba(0, 0, 0, 0.55)');
+
+    ctx.save();
+    ctx.filter = 'none';
+    ctx.fillStyle = mask;
+    ctx.fillRect(0, 0, width, height);
+    ctx.restore();
+}
+
+/**
+ * Helper: Darken the video rect outside a soft-edged circle around the cursor
+ */
+function drawSpotlight(
+    ctx: CanvasRenderingContext2D | OffscreenCanvasRenderingContext2D,
+    cursorX: number,
+    cursorY: number,
+    videoRect: { x: number; y: number; width: number; height: number },
+    spotlight: SpotlightSettings,
+    sourceScale: number
+): void {
+    const radius = spotlight.radius * sourceScale;
+    const dim = `rgba(0, 0, 0, ${spotlight.dimOpacity})`;
+
+    const mask = ctx.createRadialGradient(cursorX, cursorY, 0, cursorX, cursorY, radius);
+    mask.addColorStop(0, 'rgba(0, 0, 0, 0)');
+    mask.addColorStop(0.75, 'rgba(0, 0, 0, 0)');  // Soft edge over the outer quarter
+    mask.addColorStop(1, dim);
+
+    ctx.save();
+    ctx.filter = 'none';
+    ctx.fillStyle = mask;
+    ctx.fillRect(videoRect.x, videoRect.y, videoRect.width, videoRect.height);
+    ctx.restore();
+}
+
+/**
+ * Helper: Draw the drop shadow of the video rectangle
+ */
+function drawShadow(
+    ctx: CanvasRenderingContext2D | OffscreenCanvasRenderingContext2D,
+    x: number,
+    y: number,
+    width: number,
+    height: number,
+    radius: number,
+    shadow: ShadowSettings,
+    sourceScale: number
+): void {
+    // Shadow offset and blur ignore the context transform, so apply the
+    // preview's device pixel ratio scale by hand
+    const deviceScale = ctx.getTransform().a;
+    const scale = sourceScale * deviceScale;
+
+    const { r, g, b } = parseHexColor(shadow.color, { r: 0, g: 0, b: 0 });
+
+    ctx.save();
+    ctx.filter = 'none';
+    ctx.shadowColor = `rgba(${r}, ${g}, ${b}, ${shadow.opacity})`;
+    ctx.shadowBlur = shadow.blur * scale;
+    ctx.shadowOffsetX = shadow.offsetX * scale;
+    ctx.shadowOffsetY = shadow.offsetY * scale;
+    ctx.fillStyle = '#000000';
+    ctx.beginPath();
+    roundRect(ctx, x, y, width, height, radius);
+    ctx.fill();
+    ctx.restore();
+}
+
 /**
  * Helper: Draw rounded rectangle path
  */
@@ -1,4 +1,4 @@
-import { EffectType, EffectConfig, EasingPreset, CanvasSettings, CursorSettings, CursorStyle } from './types';
+import { EffectType, EffectConfig, EasingPreset, EasingCurve, CanvasSettings, CursorSettings, CursorStyle, AspectRatio } from './types';
 
 export const EFFECT_CONFIG: Record<EffectType, EffectConfig> = {
     zoom: { label: 'Zoom', color: '#10b981', defaultDuration: 2 },
@@ -6,6 +6,9 @@ export const EFFECT_CONFIG: Record<EffectType, EffectConfig> = {
     slowmo: { label: 'Slow-Mo', color: '#f59e0b', defaultDuration: 3 },
 };
 
+// Smallest zoom scale; below 1 the video zooms out to show more background
+export const ZOOM_MIN_SCALE = 0.5;
+
 // Zoom easing presets - duration in seconds
 export const ZOOM_EASING_PRESETS: Record<EasingPreset, { duration: number; label: string }> = {
     slow: { duration: 0.5, label: 'Slow' },
@@ -14,14 +17,46 @@ export const ZOOM_EASING_PRESETS: Record<EasingPreset, { duration: number; label
     rapid: { duration: 0.1, label: 'Rapid' },
 };
 
+// Zoom easing curves
+export const ZOOM_EASING_CURVES: Record<EasingCurve, { label: string }> = {
+    smooth: { label: 'Smooth' },
+    'ease-in': { label: 'Ease In' },
+    'ease-out': { label: 'Ease Out' },
+    'ease-in-out-cubic': { label: 'Cubic' },
+    'ease-out-back': { label: 'Back' },
+    linear: { label: 'Linear' },
+};
+
 // Default canvas settings
 export const DEFAULT_CANVAS_SETTINGS: CanvasSettings = {
     backgroundColor: '#1a1a2e',
+    backgroundGradient: null,
+    backgroundImage: null,
+    backgroundBlur: 0,
+    vignette: false,
     borderRadius: 12,
     paddingPercent: 5,
-    clickRippleEnabled: false,
+    shadow: {
+        enabled: false,
+        offsetX: 0,
+        offsetY: 12,
+        blur: 40,
+        color: '#000000',
+        opacity: 0.5,
+    },
+    aspectRatio: 'original',
+    aspectFit: 'letterbox',
 };
 
+// Output aspect ratio options
+export const ASPECT_RATIO_OPTIONS: { value: AspectRatio; label: string }[] = [
+    { value: 'original', label: 'Original' },
+    { value: '16:9', label: '16:9' },
+    { value: '9:16', label: '9:16' },
+    { value: '1:1', label: '1:1' },
+    { value: '4:5', label: '4:5' },
+];
+
 // Cursor style options
 export const CURSOR_STYLES: { value: CursorStyle; label: string }[] = [
     { value: 'pointer', label: 'Pointer' },
@@ -35,9 +70,21 @@ export const DEFAULT_CURSOR_SETTINGS: CursorSettings = {
     style: 'pointer',
     size: 24,
     color: '#ffffff',
+    opacity: 1,
+    outline: true,
+    outlineColor: '#000000',
+    customImage: null,
     smoothing: 0.15,        // Lerp factor (higher = smoother)
     velocityScale: true,    // Enlarge on fast movement
+    trailLength: 0,         // No cursor trail
     clickRipple: true,      // Show click ripples
+    rippleColor: '#ffffff',
+    rippleSize: 50,
+    spotlight: {
+        enabled: false,
+        radius: 200,
+        dimOpacity: 0.6,
+    },
 };
 
 // Export resolution options
@@ -61,9 +108,31 @@ export const QUALITY_OPTIONS = [
     { value: 'low', label: 'Low' },
 ] as const;
 
+// Export encoder options (hardware ones need a matching GPU and FFmpeg build)
+export const ENCODER_OPTIONS = [
+    { value: 'software', label: 'Software (x264)' },
+    { value: 'nvenc', label: 'NVIDIA NVENC' },
+    { value: 'qsv', label: 'Intel Quick Sync' },
+    { value: 'amf', label: 'AMD AMF' },
+] as const;
+
+// Export playback speed options
+export const SPEED_OPTIONS = [
+    { value: 1, label: '1x' },
+    { value: 1.25, label: '1.25x' },
+    { value: 1.5, label: '1.5x' },
+    { value: 2, label: '2x' },
+] as const;
+
 // Default export settings
 export const DEFAULT_EXPORT_SETTINGS = {
     resolution: 'original' as const,
     format: 'mp4' as const,
     quality: 'high' as const,
+    encoder: 'software' as const,
+    maxFileSizeMb: null,
+    fadeInSecs: 0,
+    fadeOutSecs: 0,
+    speed: 1,
+    normalizeAudio: false,
 };
@@ -11,7 +11,7 @@
  * - Output is a FrameState that can be applied to any rendering target
  */
 
-import { Effect, CursorPosition, CursorSettings, EasingPreset } from './types';
+import { Effect, CursorPosition, CursorSettings, EasingPreset, EasingCurve } from './types';
 import { ZOOM_EASING_PRESETS } from './constants';
 
 // ============================================================================
@@ -33,6 +33,7 @@ export interface FrameState {
 
     // Visual effects
     blurIntensity: number;   // 0 = no blur, higher = more blur
+    backgroundDim: number;   // 0 = none, 1 = black padding (ramps with the zoom)
 
     // Active effects (for debugging/visualization)
     activeZoomId: string | null;
@@ -67,6 +68,35 @@ export function smoothstep(t: number): number {
     return clamped * clamped * (3 - 2 * clamped);
 }
 
+/**
+ * Apply a zoom easing curve to progress t (0-1)
+ * 
+ * 'ease-out-back' overshoots past 1 (about 10%) before settling, so the zoom
+ * briefly goes slightly past its scale; the others stay within 0-1.
+ */
+export function applyEasingCurve(curve: EasingCurve | undefined, t: number): number {
+    const clamped = Math.max(0, Math.min(1, t));
+    switch (curve) {
+        case 'linear':
+            return clamped;
+        case 'ease-in':
+            return clamped * clamped;
+        case 'ease-out':
+            return 1 - (1 - clamped) * (1 - clamped);
+        case 'ease-in-out-cubic':
+            return clamped < 0.5
+                ? 4 * clamped * clamped * clamped
+                : 1 - Math.pow(-2 * clamped + 2, 3) / 2;
+        case 'ease-out-back': {
+            const c1 = 1.70158;
+            const c3 = c1 + 1;
+            return 1 + c3 * Math.pow(clamped - 1, 3) + c1 * Math.pow(clamped - 1, 2);
+        }
+        default:
+            return smoothstep(clamped);
+    }
+}
+
 /**
  * Linear interpolation between two values
  */
@@ -155,6 +185,16 @@ export function getEasingDuration(easing: EasingPreset | undefined): number {
     return ZOOM_EASING_PRESETS[preset]?.duration || 0.35;
 }
 
+/**
+ * Get the zoom in/out duration of an effect: its explicit easeDuration if set,
+ * otherwise its preset's. Capped at half the effect's length so the zoom-out
+ * never starts before the zoom-in would have finished.
+ */
+export function getZoomEaseDuration(effect: Effect): number {
+    const duration = effect.easeDuration ?? getEasingDuration(effect.easing);
+    return Math.max(0.01, Math.min(duration, (effect.endTime - effect.startTime) / 2));
+}
+
 // ============================================================================
 // VIEWPORT PANNING (Smart Camera Following)
 // ============================================================================
@@ -182,6 +222,9 @@ export function computeSmartPan(
     const INNER_MARGIN = 0.15;  // 15% margin for outer container
     const PAN_SPEED = 0.08;     // How fast viewport moves when panning
 
+    // Zoomed out (or not zoomed) the whole video is visible, so stay centered
+    if (zoomScale <= 1) return { x: 0.5, y: 0.5 };
+
     // Calculate visible viewport size at current zoom
     // At scale S, viewport shows 1/S of the video in each dimension
     const halfViewport = 0.5 / zoomScale;
@@ -250,6 +293,7 @@ export function computeFrameState(
         cursorVisible: cursorSettings.visible && cursorPositions.length > 0,
         cursorScale: 1.0,
         blurIntensity: 0,
+        backgroundDim: 0,
         activeZoomId: null,
     };
 
@@ -262,7 +306,7 @@ export function computeFrameState(
     for (const effect of effects) {
         if (effect.type !== 'zoom') continue;
 
-        const easingDuration = getEasingDuration(effect.easing);
+        const easingDuration = getZoomEaseDuration(effect);
         const anticipationStart = effect.startTime - easingDuration;
 
         if (time >= anticipationStart && time <= effect.endTime) {
@@ -272,7 +316,7 @@ export function computeFrameState(
     }
 
     if (activeZoom) {
-        const easingDuration = getEasingDuration(activeZoom.easing);
+        const easingDuration = getZoomEaseDuration(activeZoom);
         const ZOOM_SCALE = activeZoom.scale || 2.0;
 
         // ANTICIPATION timing
@@ -280,10 +324,12 @@ export function computeFrameState(
         const timeFromAnticipation = time - anticipationStart;
         const timeToEnd = activeZoom.endTime - time;
 
-        // Initialize viewport for new effect
+        // Initialize viewport for new effect. Zooming out shows the whole
+        // video, so it stays centered instead of pulling toward the target
         if (viewportState.lastEffectId !== activeZoom.id) {
-            viewportState.x = activeZoom.targetX ?? 0.5;
-            viewportState.y = activeZoom.targetY ?? 0.5;
+            const zoomsOut = ZOOM_SCALE < 1;
+            viewportState.x = zoomsOut ? 0.5 : activeZoom.targetX ?? 0.5;
+            viewportState.y = zoomsOut ? 0.5 : activeZoom.targetY ?? 0.5;
             viewportState.lastEffectId = activeZoom.id;
         }
 
@@ -305,16 +351,16 @@ export function computeFrameState(
             }
         }
 
-        // Compute zoom intensity using smoothstep
+        // Compute zoom intensity using the effect's easing curve
         let zoomIntensity: number;
         if (timeFromAnticipation < easingDuration) {
             // Zooming IN (anticipation phase before the click)
             const t = timeFromAnticipation / easingDuration;
-            zoomIntensity = smoothstep(t);
+            zoomIntensity = applyEasingCurve(activeZoom.curve, t);
         } else if (timeToEnd < easingDuration) {
-            // Zooming OUT
+            // Zooming OUT (mirrored, so an overshoot happens at the zoomed-in end)
             const t = timeToEnd / easingDuration;
-            zoomIntensity = smoothstep(t);
+            zoomIntensity = applyEasingCurve(activeZoom.curve, t);
         } else {
             // Hold phase (fully zoomed)
             zoomIntensity = 1;
@@ -324,6 +370,9 @@ export function computeFrameState(
         result.viewportX = viewportX;
         result.viewportY = viewportY;
         result.activeZoomId = activeZoom.id;
+
+        // Dim ramps in and out with the zoom, without its overshoot
+        result.backgroundDim = clamp(activeZoom.dimBackground ?? 0, 0, 1) * clamp(zoomIntensity, 0, 1);
     } else {
         // No active zoom - reset viewport
         viewportState.x = 0.5;
@@ -386,18 +435,24 @@ export function computeFrameState(
  * 
  * FIRST PRINCIPLES:
  * - Canvas origin is at (0, 0) top-left
+ * - The video is fitted inside the padded canvas keeping its aspect ratio
+ *   (letterboxed when the canvas has a different shape)
  * - We need to translate so that (viewportX, viewportY) appears at canvas center
  * - Then scale around that point
  * 
- * Formula matches VideoPreview.tsx:
- *   translateX = (0.5 - viewportX) * (scale - 1) * canvasWidth
- *   translateY = (0.5 - viewportY) * (scale - 1) * canvasHeight
+ * Offsets are relative to the fitted video, not the canvas, so zoom
+ * targeting stays correct when the two have different aspect ratios:
+ *   translateX = (0.5 - viewportX) * (scale - 1) * fittedWidth
+ *   translateY = (0.5 - viewportY) * (scale - 1) * fittedHeight
+ * 
+ * @param videoAspect - Width / height of the video content (defaults to the canvas aspect)
  */
 export function computeCanvasTransform(
     frameState: FrameState,
     canvasWidth: number,
     canvasHeight: number,
-    paddingPercent: number
+    paddingPercent: number,
+    videoAspect: number = canvasWidth / canvasHeight
 ): {
     translateX: number;
     translateY: number;
@@ -409,11 +464,19 @@ export function computeCanvasTransform(
 } {
     // Base scale from padding
     const baseScale = 1.0 - (2 * paddingPercent / 100);
-    const effectiveScale = baseScale * frameState.scale;
+
+    // Video fitted inside the padded canvas, before zoom
+    let fittedWidth = canvasWidth * baseScale;
+    let fittedHeight = canvasHeight * baseScale;
+    if (videoAspect > fittedWidth / fittedHeight) {
+        fittedHeight = fittedWidth / videoAspect;
+    } else {
+        fittedWidth = fittedHeight * videoAspect;
+    }
 
     // Video dimensions at current scale
-    const videoWidth = canvasWidth * effectiveScale;
-    const videoHeight = canvasHeight * effectiveScale;
+    const videoWidth = fittedWidth * frameState.scale;
+    const videoHeight = fittedHeight * frameState.scale;
 
     // Centered position
     const centeredX = (canvasWidth - videoWidth) / 2;
@@ -421,8 +484,8 @@ export function computeCanvasTransform(
 
     // Offset for viewport targeting
     // This makes (viewportX, viewportY) appear at canvas center
-    const offsetX = (0.5 - frameState.viewportX) * (frameState.scale - 1) * canvasWidth * baseScale;
-    const offsetY = (0.5 - frameState.viewportY) * (frameState.scale - 1) * canvasHeight * baseScale;
+    const offsetX = (0.5 - frameState.viewportX) * (frameState.scale - 1) * fittedWidth;
+    const offsetY = (0.5 - frameState.viewportY) * (frameState.scale - 1) * fittedHeight;
 
     return {
         translateX: offsetX,
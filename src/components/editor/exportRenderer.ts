@@ -18,7 +18,9 @@
 
 import {
     renderFrame,
+    drawFade,
     createOffscreenContext,
+    loadImage,
     createViewportState,
     createCursorState,
     RenderContext,
@@ -26,7 +28,9 @@ import {
     ViewportState,
     CursorState,
 } from './canvasRenderer';
-import { Effect, CursorPosition, CursorSettings, CanvasSettings } from './types';
+import { Effect, CursorPosition, ClickEvent, CursorSettings, CanvasSettings } from './types';
+import { ZOOM_MIN_SCALE } from './constants';
+import { computeFrameState } from './effectEngine';
 
 // ============================================================================
 // TYPES
@@ -47,10 +51,18 @@ export interface ExportConfig {
     effects: Effect[];
     /** Cursor position data */
     cursorPositions: CursorPosition[];
+    /** Click events (for click ripples) */
+    clickEvents: ClickEvent[];
     /** Cursor rendering settings */
     cursorSettings: CursorSettings;
     /** Canvas/background settings */
     canvasSettings: CanvasSettings;
+    /** Fade in from the background over this many seconds (0 = off) */
+    fadeInSecs?: number;
+    /** Fade out to the background over this many seconds (0 = off) */
+    fadeOutSecs?: number;
+    /** Playback speed (2 = twice as fast, half as long); 1 by default */
+    speed?: number;
 }
 
 export interface ExportProgress {
@@ -101,6 +113,77 @@ export function extractFrameDataRGB(ctx: RenderContext): Uint8Array {
     return rgb;
 }
 
+// ============================================================================
+// FADES
+// ============================================================================
+
+/**
+ * How much of the background covers a frame (0-1)
+ * 
+ * Fades are measured in output time (after any speed change), from the
+ * first frame to the last one, so the last frame ends fully faded.
+ * encode_frames fades the soundtrack over the same seconds.
+ * 
+ * @param frameIndex - Index of the frame being rendered
+ * @param totalFrames - Number of frames in the export
+ */
+function fadeAmount(frameIndex: number, totalFrames: number, fps: number, config: ExportConfig): number {
+    const fadeIn = config.fadeInSecs ?? 0;
+    const fadeOut = config.fadeOutSecs ?? 0;
+    const elapsed = frameIndex / fps;
+    const remaining = (totalFrames - 1 - frameIndex) / fps;
+    let amount = 0;
+    if (fadeIn > 0) {
+        amount = Math.max(amount, 1 - elapsed / fadeIn);
+    }
+    if (fadeOut > 0) {
+        amount = Math.max(amount, 1 - remaining / fadeOut);
+    }
+    return Math.min(Math.max(amount, 0), 1);
+}
+
+// ============================================================================
+// EFFECT VALIDATION
+// ============================================================================
+
+/**
+ * Check effects before rendering and correct what can be corrected
+ * 
+ * FIRST PRINCIPLES:
+ * - Inverted time ranges are swapped rather than silently never showing
+ * - Zoom scale is at least ZOOM_MIN_SCALE (below 1 zooms out)
+ * - Zoom targets are clamped to the video (0-1)
+ * - Times that aren't finite numbers can't be fixed, so they fail the export
+ *   with the index of every such effect
+ */
+export function validateEffects(effects: Effect[]): Effect[] {
+    const invalid: string[] = [];
+    const validated = effects.map((effect: Effect, index: number): Effect => {
+        if (!Number.isFinite(effect.startTime) || !Number.isFinite(effect.endTime)) {
+            invalid.push(`#${index} (${effect.type}): start ${effect.startTime}, end ${effect.endTime}`);
+            return effect;
+        }
+
+        const fixed: Effect = { ...effect };
+        if (fixed.endTime < fixed.startTime) {
+            console.warn(`[ExportRenderer] Effect #${index} ends before it starts, swapping its times`);
+            fixed.startTime = effect.endTime;
+            fixed.endTime = effect.startTime;
+        }
+        if (fixed.type === 'zoom') {
+            if (fixed.scale !== undefined) fixed.scale = Math.max(fixed.scale, ZOOM_MIN_SCALE);
+            if (fixed.targetX !== undefined) fixed.targetX = Math.min(Math.max(fixed.targetX, 0), 1);
+            if (fixed.targetY !== undefined) fixed.targetY = Math.min(Math.max(fixed.targetY, 0), 1);
+        }
+        return fixed;
+    });
+
+    if (invalid.length > 0) {
+        throw new Error(`Invalid effect times: ${invalid.join('; ')}`);
+    }
+    return validated;
+}
+
 // ============================================================================
 // VIDEO FRAME SEEKING
 // ============================================================================
@@ -131,6 +214,25 @@ function seekVideo(video: HTMLVideoElement, time: number): Promise<void> {
     });
 }
 
+/**
+ * Make sure the background and custom cursor images are decoded before the
+ * first frame, so it doesn't render without them
+ */
+async function preloadImages(canvasSettings: CanvasSettings, cursorSettings: CursorSettings): Promise<void> {
+    if (canvasSettings.backgroundImage) {
+        const image = await loadImage(canvasSettings.backgroundImage);
+        if (!image) {
+            console.warn('[ExportRenderer] Background image failed to load, using background color');
+        }
+    }
+    if (cursorSettings.style === 'custom' && cursorSettings.customImage) {
+        const image = await loadImage(cursorSettings.customImage);
+        if (!image) {
+            console.warn('[ExportRenderer] Custom cursor image failed to load, using pointer');
+        }
+    }
+}
+
 // ============================================================================
 // MAIN EXPORT FUNCTION
 // ============================================================================
@@ -164,6 +266,7 @@ export async function exportFrames(
         endTime,
         effects,
         cursorPositions,
+        clickEvents,
         cursorSettings,
         canvasSettings,
     } = config;
@@ -171,14 +274,20 @@ export async function exportFrames(
     // Create rendering context
     const renderCtx = createOffscreenContext(width, height);
 
+    await preloadImages(canvasSettings, cursorSettings);
+
     // Initialize state (persists across frames for smooth transitions)
     const viewportState: ViewportState = createViewportState();
     const cursorState: CursorState = createCursorState();
 
-    // Calculate frame timing
-    const duration = endTime - startTime;
+    // Calculate frame timing. Speed changes how far the source advances per
+    // output frame; effects and cursor data stay in source time, so they
+    // line up without rescaling. encode_frames speeds the soundtrack up to match
+    const speed = config.speed ?? 1;
+    const duration = (endTime - startTime) / speed;
     const totalFrames = Math.ceil(duration * fps);
     const frameDuration = 1 / fps;
+    const sourceStep = frameDuration * speed;
 
     const startMs = performance.now();
 
@@ -186,6 +295,7 @@ export async function exportFrames(
     const renderOptions: RenderOptions = {
         effects,
         cursorPositions,
+        clickEvents,
         cursorSettings,
         canvasSettings,
         viewportState,
@@ -193,27 +303,28 @@ export async function exportFrames(
     };
 
     // Pre-filter effects to only those in the export range
-    const relevantEffects = effects.filter(e =>
+    const relevantEffects = validateEffects(effects).filter(e =>
         (e.startTime <= endTime && e.endTime >= startTime)
     );
     renderOptions.effects = relevantEffects;
 
     console.log(`[ExportRenderer] Starting export: ${totalFrames} frames at ${fps}fps`);
     console.log(`[ExportRenderer] Resolution: ${width}x${height}`);
-    console.log(`[ExportRenderer] Time range: ${startTime.toFixed(2)}s - ${endTime.toFixed(2)}s`);
+    console.log(`[ExportRenderer] Time range: ${startTime.toFixed(2)}s - ${endTime.toFixed(2)}s at ${speed}x`);
 
     // Pause video during export
     video.pause();
 
     // Render each frame
     for (let frameIndex = 0; frameIndex < totalFrames; frameIndex++) {
-        const time = startTime + frameIndex * frameDuration;
+        const time = startTime + frameIndex * sourceStep;
 
         // Seek video to frame time
         await seekVideo(video, time);
 
-        // Render frame using unified renderer
+        // Render frame using unified renderer, then apply any fade
         renderFrame(video, time, renderCtx, renderOptions);
+        drawFade(renderCtx, canvasSettings, fadeAmount(frameIndex, totalFrames, fps, config));
 
         // Extract raw RGB pixel data
         const frameData = extractFrameDataRGB(renderCtx);
@@ -281,23 +392,32 @@ export async function exportFramesBatched(
         endTime,
         effects,
         cursorPositions,
+        clickEvents,
         cursorSettings,
         canvasSettings,
     } = config;
 
     const renderCtx = createOffscreenContext(width, height);
+    if (canvasSettings.backgroundImage) {
+        await loadImage(canvasSettings.backgroundImage);
+    }
+    if (cursorSettings.style === 'custom' && cursorSettings.customImage) {
+        await loadImage(cursorSettings.customImage);
+    }
     const viewportState: ViewportState = createViewportState();
     const cursorState: CursorState = createCursorState();
 
-    const duration = endTime - startTime;
+    const speed = config.speed ?? 1;
+    const duration = (endTime - startTime) / speed;
     const totalFrames = Math.ceil(duration * fps);
-    const frameDuration = 1 / fps;
+    const sourceStep = speed / fps;
 
     const startMs = performance.now();
 
     const renderOptions: RenderOptions = {
-        effects: effects.filter(e => e.startTime <= endTime && e.endTime >= startTime),
+        effects: validateEffects(effects).filter(e => e.startTime <= endTime && e.endTime >= startTime),
         cursorPositions,
+        clickEvents,
         cursorSettings,
         canvasSettings,
         viewportState,
@@ -310,10 +430,11 @@ export async function exportFramesBatched(
     let batchStartIndex = 0;
 
     for (let frameIndex = 0; frameIndex < totalFrames; frameIndex++) {
-        const time = startTime + frameIndex * frameDuration;
+        const time = startTime + frameIndex * sourceStep;
 
         await seekVideo(video, time);
         renderFrame(video, time, renderCtx, renderOptions);
+        drawFade(renderCtx, canvasSettings, fadeAmount(frameIndex, totalFrames, fps, config));
         batch.push(extractFrameDataRGB(renderCtx));
 
         // When batch is full, send it
@@ -339,6 +460,60 @@ export async function exportFramesBatched(
     }
 }
 
+// ============================================================================
+// STILL FRAME EXPORT
+// ============================================================================
+
+/** Settings for a single frame: an export without a time range or timing */
+export type StillFrameConfig = Omit<ExportConfig, 'fps' | 'startTime' | 'endTime' | 'fadeInSecs' | 'fadeOutSecs' | 'speed'>;
+
+/** How far back to replay effect state before a still frame */
+const STILL_WARMUP_SECS = 2;
+const STILL_WARMUP_FPS = 60;
+
+/**
+ * Render the frame at one timestamp, for saving a screenshot
+ * 
+ * FIRST PRINCIPLES:
+ * - Same renderer as preview and export, so the image matches the preview
+ * - Cursor smoothing and zoom panning carry state from frame to frame, so
+ *   the couple of seconds before `time` are replayed (state only, no
+ *   drawing) to settle them where playback would have them
+ * 
+ * @returns Raw RGB pixels, like the frames exportFrames produces
+ */
+export async function renderStillFrame(
+    video: HTMLVideoElement,
+    time: number,
+    config: StillFrameConfig
+): Promise<Uint8Array> {
+    const { width, height, cursorPositions, clickEvents, cursorSettings, canvasSettings } = config;
+    const effects = validateEffects(config.effects);
+
+    const renderCtx = createOffscreenContext(width, height);
+    await preloadImages(canvasSettings, cursorSettings);
+
+    const viewportState = createViewportState();
+    const cursorState = createCursorState();
+    for (let t = Math.max(0, time - STILL_WARMUP_SECS); t < time; t += 1 / STILL_WARMUP_FPS) {
+        computeFrameState(t, effects, cursorPositions, cursorSettings, viewportState, cursorState);
+    }
+
+    video.pause();
+    await seekVideo(video, time);
+    renderFrame(video, time, renderCtx, {
+        effects,
+        cursorPositions,
+        clickEvents,
+        cursorSettings,
+        canvasSettings,
+        viewportState,
+        cursorState,
+    });
+
+    return extractFrameDataRGB(renderCtx);
+}
+
 // ============================================================================
 // UTILITY FUNCTIONS
 // ============================================================================
@@ -346,8 +521,8 @@ export async function exportFramesBatched(
 /**
  * Calculate total frame count for an export
  */
-export function calculateFrameCount(startTime: number, endTime: number, fps: number): number {
-    return Math.ceil((endTime - startTime) * fps);
+export function calculateFrameCount(startTime: number, endTime: number, fps: number, speed: number = 1): number {
+    return Math.ceil((endTime - startTime) / speed * fps);
 }
 
 /**
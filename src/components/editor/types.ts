@@ -3,8 +3,10 @@ export interface ClickEvent {
     timestamp_ms: number;
     x: number;
     y: number;
+    button: 'left' | 'right' | 'middle';
     is_double_click: boolean;
     is_triple_click: boolean;  // True if this was a triple-click (zoom trigger)
+    zoom_scale: number | null; // Zoom factor picked while recording, for triple-clicks
 }
 
 // Cursor position from recording (for cursor-following zoom)
@@ -27,12 +29,44 @@ export type EffectType = 'zoom' | 'blur' | 'slowmo';
 // Zoom easing presets
 export type EasingPreset = 'slow' | 'mellow' | 'quick' | 'rapid';
 
+// Shape of the zoom in/out animation (presets set its duration)
+export type EasingCurve = 'smooth' | 'ease-in' | 'ease-out' | 'ease-in-out-cubic' | 'ease-out-back' | 'linear';
+
+// Linear background gradient (angle in degrees, CSS convention: 0 = bottom to top, 90 = left to right)
+export interface BackgroundGradient {
+    from: string;
+    to: string;
+    angle: number;
+}
+
+// Drop shadow under the video (sizes in source video pixels, like borderRadius)
+export interface ShadowSettings {
+    enabled: boolean;
+    offsetX: number;
+    offsetY: number;
+    blur: number;
+    color: string;             // Hex color
+    opacity: number;           // 0-1
+}
+
+// Output frame shape; 'original' follows the recording
+export type AspectRatio = 'original' | '16:9' | '9:16' | '1:1' | '4:5';
+
+// How the video fits a frame of a different shape
+export type AspectFit = 'letterbox' | 'crop';
+
 // Canvas styling settings
 export interface CanvasSettings {
     backgroundColor: string;
+    backgroundGradient: BackgroundGradient | null;  // Replaces the solid color when set
+    backgroundImage: string | null;  // Image URL drawn behind the video (cover fit), null for solid color
+    backgroundBlur: number;    // 0 for none, else blur (px at 1080p) of the video itself filling the background
+    vignette: boolean;         // Darken the corners of the finished frame
     borderRadius: number;      // 0-32px
     paddingPercent: number;    // 0-20%
-    clickRippleEnabled: boolean;
+    shadow: ShadowSettings;
+    aspectRatio: AspectRatio;
+    aspectFit: AspectFit;      // Letterbox on the background, or crop to fill
 }
 
 // Unified effect interface with lane support
@@ -49,6 +83,9 @@ export interface Effect {
     intensity?: number;
     speed?: number;
     easing?: EasingPreset; // Zoom animation speed preset
+    curve?: EasingCurve;   // Zoom animation shape, 'smooth' when unset
+    easeDuration?: number; // Zoom in/out seconds, overrides the preset's when set
+    dimBackground?: number; // 0-1 darkening of the padding while zoomed, 0 when unset
 }
 
 // Effect configuration type
@@ -62,18 +99,39 @@ export interface EffectConfig {
 export type ExportResolution = '720p' | '1080p' | '4k' | 'original';
 export type ExportFormat = 'mp4' | 'webm';
 export type ExportQuality = 'low' | 'medium' | 'high';
+export type ExportEncoder = 'software' | 'nvenc' | 'qsv' | 'amf';
 
 export interface ExportSettings {
     resolution: ExportResolution;
     format: ExportFormat;
     quality: ExportQuality;
+    encoder: ExportEncoder;    // Hardware encoders only apply to MP4
+    maxFileSizeMb: number | null;  // Encode to fit this size (e.g. an upload limit), null for quality-based
+    fadeInSecs: number;     // Fade in from the background (0 = off)
+    fadeOutSecs: number;    // Fade out to the background (0 = off)
+    speed: number;          // Playback speed multiplier (1 = original)
+    normalizeAudio: boolean; // Bring the audio to a consistent loudness (-16 LUFS)
+}
+
+// Payload of "export-finished": the outcome of the encode_frames call that returned `id`
+export interface ExportFinished {
+    id: number;
+    output_path: string | null;
+    error: unknown;  // ExportError ({ kind, ... }) when the export failed
 }
 
 // Sidebar tab types
 export type SidebarTab = 'background' | 'cursor' | 'export' | 'effects';
 
 // Cursor style options
-export type CursorStyle = 'pointer' | 'circle' | 'crosshair';
+export type CursorStyle = 'pointer' | 'circle' | 'crosshair' | 'custom';
+
+// Dims the video outside a circle around the cursor (radius in source video pixels)
+export interface SpotlightSettings {
+    enabled: boolean;
+    radius: number;
+    dimOpacity: number;     // 0-1, darkness outside the spotlight
+}
 
 // Cursor settings for custom cursor rendering
 export interface CursorSettings {
@@ -81,7 +139,15 @@ export interface CursorSettings {
     style: CursorStyle;
     size: number;           // 16-48px
     color: string;          // Cursor color
+    opacity: number;        // 0-1
+    outline: boolean;       // Stroke the pointer and circle shapes
+    outlineColor: string;   // Outline color (e.g. white on dark backgrounds)
+    customImage: string | null;  // Image URL for the 'custom' style
     smoothing: number;      // 0-1 (lerp factor, higher = smoother)
     velocityScale: boolean; // Enlarge cursor on fast movement
+    trailLength: number;    // Fading trail behind the cursor, in ms (0 = off)
     clickRipple: boolean;   // Show ripple effect on clicks
+    rippleColor: string;    // Ripple ring color
+    rippleSize: number;     // Radius the ripple expands to, in source video pixels
+    spotlight: SpotlightSettings;
 }
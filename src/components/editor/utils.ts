@@ -1,3 +1,5 @@
+import { AspectRatio, ClickEvent, EasingPreset, Effect } from './types';
+
 // Check if two time ranges overlap
 export const rangesOverlap = (s1: number, e1: number, s2: number, e2: number): boolean => {
     return s1 < e2 && e1 > s2;
@@ -28,3 +30,87 @@ export const generateTimeMarkers = (timelineDuration: number): number[] => {
     }
     return markers;
 };
+
+// Width / height of the output frame for an aspect ratio setting
+export const aspectRatioValue = (aspectRatio: AspectRatio, videoWidth: number, videoHeight: number): number => {
+    if (aspectRatio === 'original') return videoWidth / videoHeight;
+    const [w, h] = aspectRatio.split(':').map(Number);
+    return w / h;
+};
+
+// Options for placing zoom effects at recorded clicks
+export interface ClickZoomOptions {
+    scale: number;       // Used when no click in a burst carries a recorded zoom_scale
+    easing: EasingPreset;
+    mergeGapMs: number;  // Clicks closer together than this share one zoom
+    holdSecs: number;    // How long the zoom stays after the last click in a group
+}
+
+// One zoom per burst of left clicks: starts at the first click, holds past
+// the last one, and targets the burst's average position. A zoom is cut short
+// where the next one starts so they never overlap on the lane. Right and
+// middle clicks (context menus, tab closes) don't zoom.
+export const zoomEffectsFromClicks = (clicks: ClickEvent[], options: ClickZoomOptions): Effect[] => {
+    const sorted = clicks
+        .filter(click => click.button === 'left')
+        .sort((a, b) => a.timestamp_ms - b.timestamp_ms);
+
+    const groups: ClickEvent[][] = [];
+    for (const click of sorted) {
+        const group = groups[groups.length - 1];
+        if (group && click.timestamp_ms - group[group.length - 1].timestamp_ms < options.mergeGapMs) {
+            group.push(click);
+        } else {
+            groups.push([click]);
+        }
+    }
+
+    return groups.map((group, index): Effect => {
+        const startTime = group[0].timestamp_ms / 1000;
+        const nextStart = index + 1 < groups.length ? groups[index + 1][0].timestamp_ms / 1000 : Infinity;
+        const endTime = Math.min(group[group.length - 1].timestamp_ms / 1000 + options.holdSecs, nextStart);
+        return {
+            id: `zoom-click-${index}-${Date.now()}`,
+            type: 'zoom',
+            startTime,
+            endTime,
+            lane: 0,
+            scale: group.find(click => click.zoom_scale != null)?.zoom_scale ?? options.scale,
+            easing: options.easing,
+            targetX: group.reduce((sum, click) => sum + click.x, 0) / group.length,
+            targetY: group.reduce((sum, click) => sum + click.y, 0) / group.length,
+        };
+    });
+};
+
+// Short, user-facing text for a failed export. encode_frames rejects with
+// { kind, ... } (see ExportError in lib.rs); other commands reject with a string
+export const exportErrorMessage = (error: unknown): string => {
+    if (typeof error === 'string') return error;
+    if (error instanceof Error) return error.message;
+    if (error && typeof error === 'object' && 'kind' in error) {
+        const failure = error as {
+            kind: string;
+            message?: string;
+            summary?: string;
+            path?: string;
+            needed_bytes?: number;
+            free_bytes?: number;
+        };
+        switch (failure.kind) {
+            case 'ffmpegNotFound':
+                return 'FFmpeg was not found. Install it or add it to PATH.';
+            case 'cancelled':
+                return 'Export cancelled';
+            case 'insufficientDiskSpace': {
+                const gb = (bytes?: number) => ((bytes ?? 0) / 1e9).toFixed(1);
+                return `Not enough disk space in ${failure.path}: needs ${gb(failure.needed_bytes)} GB, ${gb(failure.free_bytes)} GB free`;
+            }
+            case 'encodeFailed':
+                return failure.summary ?? 'Encoding failed';
+            default:
+                return failure.message ?? failure.kind;
+        }
+    }
+    return 'Unknown error';
+};